@@ -5,30 +5,290 @@ use std::ops::Not;
 use syn::parse::ParseStream;
 use syn::{Attribute, Error, Meta, Path, PathSegment, Token, TypePath};
 
+#[derive(Default)]
 pub struct CallMethodAttribute {
-    name: syn::Ident,
+    name: Option<syn::Ident>,
+    /// Explicit generic arguments of the called method (`#[call(parse::<u32>)]`).
+    turbofish: Option<syn::AngleBracketedGenericArguments>,
+    /// Path of a function that is called with the delegated expression as its first argument
+    /// (`#[call(Inner::method)]` or `#[call(<Inner as Trait>::method)]`).
+    function: Option<syn::ExprPath>,
+}
+
+impl CallMethodAttribute {
+    pub fn turbofish(&self) -> Option<&syn::AngleBracketedGenericArguments> {
+        self.turbofish.as_ref()
+    }
+
+    pub fn function_path(&self) -> Option<&syn::ExprPath> {
+        self.function.as_ref()
+    }
 }
 
 impl syn::parse::Parse for CallMethodAttribute {
     fn parse(input: ParseStream) -> Result<Self, Error> {
-        Ok(CallMethodAttribute {
-            name: input.parse()?,
+        let mut path: syn::ExprPath = input.parse().map_err(|error| {
+            Error::new(
+                input.span(),
+                format!("{error}\nExpected method name or path, e.g. #[call(parse::<u32>)] or #[call(Inner::method)]"),
+            )
+        })?;
+        let last = path
+            .path
+            .segments
+            .last()
+            .ok_or_else(|| Error::new_spanned(&path, "Expected method name"))?;
+        let name = Some(last.ident.clone());
+
+        if path.qself.is_none()
+            && path.path.leading_colon.is_none()
+            && path.path.segments.len() == 1
+        {
+            let segment = path.path.segments.pop().unwrap().into_value();
+            let turbofish = match segment.arguments {
+                syn::PathArguments::None => None,
+                syn::PathArguments::AngleBracketed(mut arguments) => {
+                    arguments.colon2_token = Some(Default::default());
+                    Some(arguments)
+                }
+                syn::PathArguments::Parenthesized(arguments) => {
+                    return Err(Error::new_spanned(arguments, "Expected generic arguments"));
+                }
+            };
+            Ok(CallMethodAttribute {
+                name,
+                turbofish,
+                function: None,
+            })
+        } else {
+            Ok(CallMethodAttribute {
+                name,
+                turbofish: None,
+                function: Some(path),
+            })
+        }
+    }
+}
+
+pub struct CallFunctionAttribute {
+    reference: Option<(Token![&], Option<Token![mut]>)>,
+    pub path: syn::ExprPath,
+}
+
+impl CallFunctionAttribute {
+    /// Returns the reference (`&` or `&mut`) that is taken of the delegated expression before
+    /// it is passed to the function.
+    pub fn reference_tokens(&self) -> TokenStream {
+        let mut tokens = TokenStream::new();
+        if let Some((ref_, mut_)) = &self.reference {
+            ref_.to_tokens(&mut tokens);
+            mut_.to_tokens(&mut tokens);
+        }
+        tokens
+    }
+}
+
+impl syn::parse::Parse for CallFunctionAttribute {
+    fn parse(input: ParseStream) -> Result<Self, Error> {
+        let mut reference = None;
+        if let Ok(ref_) = input.parse::<syn::Token![&]>() {
+            reference = Some((ref_, input.parse::<syn::Token![mut]>().ok()));
+        }
+        let path = input.parse().map_err(|error| {
+            Error::new(
+                input.span(),
+                format!("{error}\nExpected function path, e.g. #[call_fn(&serde_json::to_string)]"),
+            )
+        })?;
+        Ok(CallFunctionAttribute { reference, path })
+    }
+}
+
+#[derive(Default)]
+pub struct CallFieldAttribute {
+    /// The field is an `Option` of a callable value.
+    optional: bool,
+    /// `#[call_field(optional)]` without a field name.
+    bare_optional: Option<syn::Ident>,
+    member: Option<MemberPath>,
+}
+
+impl CallFieldAttribute {
+    /// Returns true if the called field `member` is an `Option`.
+    /// A bare `#[call_field(optional)]` on a method named `optional` calls the `optional` field.
+    pub fn is_optional(&self, member: &MemberPath) -> bool {
+        match &self.bare_optional {
+            Some(keyword) if member.is_ident(keyword) => false,
+            _ => self.optional,
+        }
+    }
+}
+
+impl syn::parse::Parse for CallFieldAttribute {
+    fn parse(input: ParseStream) -> Result<Self, Error> {
+        let mut optional = false;
+        let mut bare_optional = None;
+        if let Some((ident, bare)) = parse_keyword(input, |ident| ident == "optional")? {
+            optional = true;
+            if bare {
+                bare_optional = Some(ident);
+            }
+        }
+        let member = input.is_empty().not().then(|| input.parse()).transpose()?;
+        Ok(CallFieldAttribute {
+            optional,
+            bare_optional,
+            member,
         })
     }
 }
 
+/// Parses a keyword (like `optional` or `clone`) at the start of a field attribute, which could
+/// also be the name of a field. The identifier is only a keyword if it is followed by a field
+/// name or if it is the only token of the attribute (`bare`), but not if it starts a nested
+/// field path (`clone.inner`).
+fn parse_keyword(
+    input: ParseStream,
+    is_keyword: impl Fn(&syn::Ident) -> bool,
+) -> Result<Option<(syn::Ident, bool)>, Error> {
+    let fork = input.fork();
+    match fork.parse::<syn::Ident>() {
+        Ok(ident) if is_keyword(&ident) && !fork.peek(Token![.]) => {
+            input.parse::<syn::Ident>()?;
+            Ok(Some((ident, input.is_empty())))
+        }
+        _ => Ok(None),
+    }
+}
+
+/// A dot-separated path of named or unnamed members, e.g. `config.limits.0.max`.
+#[derive(Clone)]
+pub struct MemberPath {
+    members: syn::punctuated::Punctuated<syn::Member, Token![.]>,
+}
+
+impl From<syn::Member> for MemberPath {
+    fn from(member: syn::Member) -> Self {
+        let mut members = syn::punctuated::Punctuated::new();
+        members.push(member);
+        MemberPath { members }
+    }
+}
+
+impl MemberPath {
+    /// Returns true if the path is a single named member equal to `ident`.
+    fn is_ident(&self, ident: &syn::Ident) -> bool {
+        self.members.len() == 1
+            && matches!(self.members.first(), Some(syn::Member::Named(member)) if member == ident)
+    }
+}
+
+impl From<syn::Ident> for MemberPath {
+    fn from(ident: syn::Ident) -> Self {
+        syn::Member::from(ident).into()
+    }
+}
+
+impl syn::parse::Parse for MemberPath {
+    fn parse(input: ParseStream) -> Result<Self, Error> {
+        let mut members = syn::punctuated::Punctuated::new();
+        loop {
+            // `a.0.1` is tokenized as `a`, `.`, `0.1`, so nested tuple indices have to be split.
+            if input.peek(syn::LitFloat) {
+                let literal: syn::LitFloat = input.parse()?;
+                for index in literal.to_string().split('.') {
+                    let index = index
+                        .parse::<u32>()
+                        .map_err(|_| Error::new(literal.span(), "Expected a tuple index"))?;
+                    if !members.empty_or_trailing() {
+                        members.push_punct(Token![.](literal.span()));
+                    }
+                    members.push_value(syn::Member::Unnamed(syn::Index {
+                        index,
+                        span: literal.span(),
+                    }));
+                }
+            } else {
+                members.push_value(input.parse()?);
+            }
+            if !input.peek(Token![.]) {
+                break;
+            }
+            members.push_punct(input.parse()?);
+        }
+        Ok(MemberPath { members })
+    }
+}
+
+impl ToTokens for MemberPath {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.members.to_tokens(tokens);
+    }
+}
+
+/// Describes how is the value of a field returned from a `#[field]` getter.
+#[derive(Clone, Copy)]
+enum FieldMode {
+    /// `#[field(clone)]`: `Clone::clone(&<expr>.field)`
+    Clone,
+    /// `#[field(copy)]`: `*&<expr>.field`
+    Copy,
+    /// `#[field(as_ref)]`: `<expr>.field.as_ref()`
+    AsRef,
+    /// `#[field(as_deref)]`: `<expr>.field.as_deref()`
+    AsDeref,
+    /// `#[field(as_mut)]`: `<expr>.field.as_mut()`
+    AsMut,
+}
+
+impl FieldMode {
+    fn from_ident(ident: &syn::Ident) -> Option<Self> {
+        let mode = match ident.to_string().as_str() {
+            "clone" => Self::Clone,
+            "copy" => Self::Copy,
+            "as_ref" => Self::AsRef,
+            "as_deref" => Self::AsDeref,
+            "as_mut" => Self::AsMut,
+            _ => return None,
+        };
+        Some(mode)
+    }
+}
+
 #[derive(Default, Clone)]
 pub struct GetFieldAttribute {
     reference: Option<(Token![&], Option<Token![mut]>)>,
-    member: Option<syn::Member>,
+    mode: Option<FieldMode>,
+    /// The mode was specified without a field name (`#[field(clone)]`).
+    bare_mode: Option<syn::Ident>,
+    member: Option<MemberPath>,
 }
 
 impl GetFieldAttribute {
-    pub fn reference_tokens(&self) -> Option<TokenStream> {
-        let (ref_, mut_) = self.reference.as_ref()?;
-        let mut tokens = ref_.to_token_stream();
-        mut_.to_tokens(&mut tokens);
-        Some(tokens)
+    /// Generates an expression that reads `member` of the delegated expression `expr`.
+    pub fn access_tokens(&self, expr: &TokenStream, member: &MemberPath) -> TokenStream {
+        let field = quote::quote! { #expr.#member };
+        // A bare `#[field(clone)]` on a method named `clone` returns the `clone` field
+        let mode = match &self.bare_mode {
+            Some(keyword) if member.is_ident(keyword) => None,
+            _ => self.mode,
+        };
+        match mode {
+            Some(FieldMode::Clone) => quote::quote! { ::core::clone::Clone::clone(&#field) },
+            Some(FieldMode::Copy) => quote::quote! { *&#field },
+            Some(FieldMode::AsRef) => quote::quote! { #field.as_ref() },
+            Some(FieldMode::AsDeref) => quote::quote! { #field.as_deref() },
+            Some(FieldMode::AsMut) => quote::quote! { #field.as_mut() },
+            None => {
+                let mut tokens = TokenStream::new();
+                if let Some((ref_, mut_)) = &self.reference {
+                    ref_.to_tokens(&mut tokens);
+                    mut_.to_tokens(&mut tokens);
+                }
+                tokens.extend(field);
+                tokens
+            }
+        }
     }
 }
 
@@ -41,8 +301,95 @@ impl syn::parse::Parse for GetFieldAttribute {
         if let Some((_, mut_)) = &mut reference {
             *mut_ = input.parse::<syn::Token![mut]>().ok();
         }
-        let member = input.is_empty().not().then(|| input.parse()).transpose()?;
-        Ok(GetFieldAttribute { reference, member })
+        let mut mode = None;
+        let mut bare_mode = None;
+        let mut member = None;
+        if let Some((ident, bare)) =
+            parse_keyword(input, |ident| FieldMode::from_ident(ident).is_some())?
+        {
+            if reference.is_some() {
+                if !bare {
+                    return Err(Error::new(
+                        ident.span(),
+                        format!("Field mode `{ident}` cannot be combined with a reference"),
+                    ));
+                }
+                // `#[field(&clone)]` returns a reference to the `clone` field
+                member = Some(ident.into());
+            } else {
+                mode = FieldMode::from_ident(&ident);
+                if bare {
+                    bare_mode = Some(ident);
+                }
+            }
+        }
+        if member.is_none() && !input.is_empty() {
+            member = Some(input.parse()?);
+        }
+        Ok(GetFieldAttribute {
+            reference,
+            mode,
+            bare_mode,
+            member,
+        })
+    }
+}
+
+#[derive(Clone, Copy)]
+pub enum FieldUpdateKind {
+    /// `#[field_set]`: `<expr>.field = value`
+    Set,
+    /// `#[field_replace]`: `mem::replace(&mut <expr>.field, value)`
+    Replace,
+    /// `#[field_take]`: `mem::take(&mut <expr>.field)`
+    Take,
+}
+
+impl FieldUpdateKind {
+    fn attribute_name(self) -> &'static str {
+        match self {
+            Self::Set => "field_set",
+            Self::Replace => "field_replace",
+            Self::Take => "field_take",
+        }
+    }
+
+    /// Prefix that is stripped from the method name when the field name is not specified.
+    fn method_prefix(self) -> &'static str {
+        match self {
+            Self::Set => "set_",
+            Self::Replace => "replace_",
+            Self::Take => "take_",
+        }
+    }
+
+    /// Number of arguments that the delegated method has to pass to the update.
+    pub fn argument_count(self) -> usize {
+        match self {
+            Self::Set | Self::Replace => 1,
+            Self::Take => 0,
+        }
+    }
+}
+
+pub struct FieldUpdateAttribute {
+    pub kind: FieldUpdateKind,
+    member: Option<MemberPath>,
+}
+
+impl FieldUpdateAttribute {
+    fn parse(attribute: &Attribute, kind: FieldUpdateKind) -> Self {
+        let member = if let syn::Meta::Path(_) = &attribute.meta {
+            None
+        } else {
+            Some(attribute.parse_args::<MemberPath>().unwrap_or_else(|_| {
+                panic!(
+                    "Cannot parse `{}` attribute, expected a field name",
+                    kind.attribute_name()
+                )
+            }))
+        };
+        FieldUpdateAttribute { kind, member }
     }
 }
 
@@ -207,20 +554,439 @@ impl TemplateExpr {
     }
 }
 
+/// Trait through which is the delegated method called (`#[through(Trait)]`).
+#[derive(Clone)]
 pub struct TraitTarget {
-    type_path: TypePath,
+    path: Path,
+    /// The trait has generic arguments, so it has to be called with a qualified path
+    /// (`<_ as Trait<A>>::method`), because `Trait<A>::method` is not a valid expression.
+    qualified: bool,
+}
+
+/// Replaces lifetimes bound by a higher-ranked trait bound (`for<'a>`) with `'_`,
+/// since they cannot be named in an expression.
+struct ElideBoundLifetimes(Vec<syn::Lifetime>);
+
+impl syn::visit_mut::VisitMut for ElideBoundLifetimes {
+    fn visit_lifetime_mut(&mut self, lifetime: &mut syn::Lifetime) {
+        if self.0.contains(lifetime) {
+            *lifetime = syn::Lifetime::new("'_", lifetime.span());
+        }
+    }
 }
 
 impl syn::parse::Parse for TraitTarget {
     fn parse(input: ParseStream) -> Result<Self, Error> {
-        let type_path: TypePath = input.parse().map_err(|error| {
+        use syn::visit_mut::VisitMut;
+
+        let bound: syn::TraitBound = input.parse().map_err(|error| {
             Error::new(
                 input.span(),
                 format!("{error}\nExpected trait path, e.g. #[through(foo::MyTrait)]"),
             )
         })?;
+        if let syn::TraitBoundModifier::Maybe(modifier) = bound.modifier {
+            return Err(Error::new_spanned(
+                modifier,
+                "Expected trait path without `?`",
+            ));
+        }
+
+        let mut path = bound.path;
+        let mut qualified = false;
+        for segment in path.segments.iter_mut() {
+            if let syn::PathArguments::AngleBracketed(arguments) = &mut segment.arguments {
+                // Associated type bindings (`Iterator<Item = u32>`) are not allowed in paths
+                // of expressions, and they are not needed to select the trait.
+                arguments.args = std::mem::take(&mut arguments.args)
+                    .into_iter()
+                    .filter(|argument| {
+                        !matches!(
+                            argument,
+                            syn::GenericArgument::AssocType(_)
+                                | syn::GenericArgument::AssocConst(_)
+                                | syn::GenericArgument::Constraint(_)
+                        )
+                    })
+                    .collect();
+                if arguments.args.is_empty() {
+                    segment.arguments = syn::PathArguments::None;
+                } else {
+                    arguments.colon2_token = None;
+                    qualified = true;
+                }
+            }
+        }
+        if let Some(lifetimes) = bound.lifetimes {
+            let lifetimes = lifetimes
+                .lifetimes
+                .into_iter()
+                .filter_map(|param| match param {
+                    syn::GenericParam::Lifetime(param) => Some(param.lifetime),
+                    _ => None,
+                })
+                .collect();
+            ElideBoundLifetimes(lifetimes).visit_path_mut(&mut path);
+        }
+
+        Ok(TraitTarget { path, qualified })
+    }
+}
+
+impl TraitTarget {
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl ToTokens for TraitTarget {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let path = &self.path;
+        if self.qualified {
+            tokens.extend(quote::quote! { <_ as #path> });
+        } else {
+            path.to_tokens(tokens);
+        }
+    }
+}
+
+/// Describes what is returned when the target of an optional (`to?`) segment is missing
+/// (`#[fallback(...)]`).
+#[derive(Clone)]
+pub enum Fallback {
+    /// `#[fallback(none)]`: returns `None`, the result of the delegated method is wrapped in `Some`.
+    None,
+    /// `#[fallback(default)]`: returns `Default::default()`.
+    Default,
+    /// `#[fallback(err(<expr>))]`: returns `Err(<expr>)`, the result of the delegated method is
+    /// wrapped in `Ok`.
+    Err(syn::Expr),
+    /// `#[fallback(panic)]` or `#[fallback(panic("message"))]`: panics.
+    Panic(Option<syn::LitStr>),
+}
+
+impl syn::parse::Parse for Fallback {
+    fn parse(input: ParseStream) -> Result<Self, Error> {
+        let ident: syn::Ident = input.parse()?;
+        let fallback = match ident.to_string().as_str() {
+            "none" => Self::None,
+            "default" => Self::Default,
+            "err" => {
+                let content;
+                syn::parenthesized!(content in input);
+                Self::Err(content.parse()?)
+            }
+            "panic" => {
+                if input.peek(syn::token::Paren) {
+                    let content;
+                    syn::parenthesized!(content in input);
+                    Self::Panic(Some(content.parse()?))
+                } else {
+                    Self::Panic(None)
+                }
+            }
+            _ => {
+                return Err(Error::new(
+                    ident.span(),
+                    "Expected one of `none`, `default`, `err(<expr>)` or `panic`",
+                ))
+            }
+        };
+        Ok(fallback)
+    }
+}
+
+/// Describes how the delegator is locked or borrowed before the delegated method is called
+/// (`#[lock(...)]`).
+#[derive(Clone)]
+pub struct LockAttribute {
+    pub kind: LockKind,
+    /// `try`: uses the non-blocking `try_*` variant of the locking method.
+    pub fallible: bool,
+    /// `err(<expr>)`: returns `Err(<expr>)` if the lock is poisoned or cannot be acquired.
+    pub error: Option<syn::Expr>,
+    /// `await`: awaits the locking method (for async locks, like `tokio::sync::Mutex`).
+    pub is_async: bool,
+}
 
-        Ok(TraitTarget { type_path })
+impl LockAttribute {
+    /// Returns the name of the method used to lock the delegator for a method that takes
+    /// `self` mutably (`mutable`) or by a shared reference.
+    pub fn method_name(&self, mutable: bool) -> String {
+        let name = self.infallible_method_name(mutable);
+        // `RefCell` can only fail through its `try_*` methods
+        if self.fallible || (self.error.is_some() && !self.kind.is_poisoning()) {
+            format!("try_{}", name)
+        } else {
+            name.to_string()
+        }
+    }
+
+    /// Returns true if the locking method returns a `Result` that is returned to the caller
+    /// (as `Err`) instead of being unwrapped.
+    pub fn returns_result(&self) -> bool {
+        self.fallible || self.error.is_some()
+    }
+
+    fn infallible_method_name(&self, mutable: bool) -> &'static str {
+        match (self.kind, mutable) {
+            (LockKind::Mutex, _) | (LockKind::Lock, _) => "lock",
+            (LockKind::RwLock, false) | (LockKind::Read, _) => "read",
+            (LockKind::RwLock, true) | (LockKind::Write, _) => "write",
+            (LockKind::RefCell, false) | (LockKind::Borrow, _) => "borrow",
+            (LockKind::RefCell, true) | (LockKind::BorrowMut, _) => "borrow_mut",
+        }
+    }
+
+    /// Returns the name of the guard returned by the locking method. Lock guards are named like
+    /// the guards of `std::sync`, `tokio::sync` and `parking_lot`.
+    pub fn guard_name(&self, mutable: bool) -> &'static str {
+        match self.infallible_method_name(mutable) {
+            "lock" => "MutexGuard",
+            "read" => "RwLockReadGuard",
+            "write" => "RwLockWriteGuard",
+            "borrow" => "Ref",
+            _ => "RefMut",
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum LockKind {
+    /// `mutex`: `lock`
+    Mutex,
+    /// `rwlock`: `read` for `&self` methods, `write` for other methods
+    RwLock,
+    /// `refcell`: `borrow` for `&self` methods, `borrow_mut` for other methods
+    RefCell,
+    /// `lock`
+    Lock,
+    /// `read`
+    Read,
+    /// `write`
+    Write,
+    /// `borrow`
+    Borrow,
+    /// `borrow_mut`
+    BorrowMut,
+}
+
+impl LockKind {
+    /// Returns true if the locking method returns a `LockResult` (which is unwrapped by default).
+    pub fn is_poisoning(self) -> bool {
+        !matches!(self, Self::RefCell | Self::Borrow | Self::BorrowMut)
+    }
+}
+
+impl syn::parse::Parse for LockAttribute {
+    fn parse(input: ParseStream) -> Result<Self, Error> {
+        let ident: syn::Ident = input.parse()?;
+        let kind = match ident.to_string().as_str() {
+            "mutex" => LockKind::Mutex,
+            "rwlock" => LockKind::RwLock,
+            "refcell" => LockKind::RefCell,
+            "lock" => LockKind::Lock,
+            "read" => LockKind::Read,
+            "write" => LockKind::Write,
+            "borrow" => LockKind::Borrow,
+            "borrow_mut" => LockKind::BorrowMut,
+            _ => {
+                return Err(Error::new(
+                    ident.span(),
+                    "Expected one of `mutex`, `rwlock`, `refcell`, `lock`, `read`, `write`, `borrow` or `borrow_mut`",
+                ))
+            }
+        };
+
+        let mut lock = LockAttribute {
+            kind,
+            fallible: false,
+            error: None,
+            is_async: false,
+        };
+        while !input.is_empty() {
+            input.parse::<Token![,]>()?;
+            if input.peek(Token![try]) {
+                input.parse::<Token![try]>()?;
+                lock.fallible = true;
+            } else if input.peek(Token![await]) {
+                input.parse::<Token![await]>()?;
+                lock.is_async = true;
+            } else {
+                let option: syn::Ident = input.parse()?;
+                if option != "err" {
+                    return Err(Error::new(
+                        option.span(),
+                        "Expected one of `try`, `await` or `err(<expr>)`",
+                    ));
+                }
+                let content;
+                syn::parenthesized!(content in input);
+                lock.error = Some(content.parse()?);
+            }
+        }
+        if lock.is_async && (lock.returns_result() || !kind.is_poisoning()) {
+            return Err(Error::new(
+                ident.span(),
+                "`await` can only be used with `mutex`, `rwlock`, `lock`, `read` or `write`, without `try` or `err`",
+            ));
+        }
+        Ok(lock)
+    }
+}
+
+/// Maps the guard returned by the delegator (e.g. a `Ref` or a lock guard) to the reference
+/// returned by the delegated method (`#[guard]` or `#[guard(path::to::map)]`).
+#[derive(Clone)]
+pub struct GuardMap {
+    /// The function used to map the guard (like `Ref::map`). If it is not specified, it is
+    /// derived from the `#[lock]` of the method or from its return type.
+    pub map: Option<syn::ExprPath>,
+}
+
+impl GuardMap {
+    /// Returns the function that maps the guard, if it was not specified.
+    /// With a `#[lock]` (locked `mutable`ly or not), it is the `map` function of the guard of the
+    /// lock (`RwLockWriteGuard::map` for `write`), in the module of the returned guard type (which
+    /// can be a different, mapped guard). `RefCell` guards use `Ref::map` and `RefMut::map`.
+    /// Otherwise, the returned guard type is used (`Ref<'_, T>` and `Result<Ref<'_, T>, E>` map
+    /// with `Ref::map`).
+    pub fn map_function(
+        &self,
+        output: &syn::ReturnType,
+        lock: Option<(&LockAttribute, bool)>,
+        method: &syn::Ident,
+    ) -> syn::ExprPath {
+        if let Some(map) = &self.map {
+            return map.clone();
+        }
+        let returned = match output {
+            syn::ReturnType::Type(_, ty) => returned_guard(ty),
+            syn::ReturnType::Default => panic!(
+                "The `guard` attribute requires a guard return type, like `Ref<'_, T>` ({})",
+                method
+            ),
+        };
+        let mut path = match (lock, returned) {
+            (Some((lock, mutable)), _) if !lock.kind.is_poisoning() => {
+                let guard = syn::Ident::new(lock.guard_name(mutable), method.span());
+                syn::parse_quote!(::core::cell::#guard)
+            }
+            (Some((lock, mutable)), returned) => {
+                let guard = syn::Ident::new(lock.guard_name(mutable), method.span());
+                match returned {
+                    Some(mut path) => {
+                        path.segments.pop();
+                        path.segments.push(guard.into());
+                        path
+                    }
+                    None => guard.into(),
+                }
+            }
+            (None, Some(path)) => path,
+            (None, None) => panic!(
+                "Cannot derive the guard type of {} from its return type, specify the map function (`#[guard(Ref::map)]`)",
+                method
+            ),
+        };
+        if let Some(last) = path.segments.last_mut() {
+            last.arguments = syn::PathArguments::None;
+        }
+        let span = method.span();
+        path.segments.push(syn::Ident::new("map", span).into());
+        syn::ExprPath {
+            attrs: vec![],
+            qself: None,
+            path,
+        }
+    }
+}
+
+/// Returns the path of the guard type returned by a method, looking through the `Result` of
+/// a fallible `lock`.
+fn returned_guard(mut ty: &syn::Type) -> Option<syn::Path> {
+    loop {
+        let path = match ty {
+            syn::Type::Path(path) if path.qself.is_none() => &path.path,
+            syn::Type::Paren(paren) => {
+                ty = &paren.elem;
+                continue;
+            }
+            _ => return None,
+        };
+        let last = path.segments.last()?;
+        // A fallible `lock` wraps the guard in a `Result`
+        if last.ident == "Result" {
+            if let syn::PathArguments::AngleBracketed(args) = &last.arguments {
+                if let Some(syn::GenericArgument::Type(inner)) = args.args.first() {
+                    ty = inner;
+                    continue;
+                }
+            }
+        }
+        return Some(path.clone());
+    }
+}
+
+/// Describes which generic arguments are passed to the delegated method (`#[generics(...)]`).
+#[derive(Clone)]
+pub enum GenericsForwarding {
+    /// `#[generics(infer)]` or `#[generics()]`: no generic arguments are passed explicitly.
+    Infer,
+    /// `#[generics(B, u32)]`: the given generic arguments are passed positionally, in the given order.
+    Explicit(syn::punctuated::Punctuated<syn::GenericArgument, Token![,]>),
+    /// `#[generics(T = Vec<T>)]`: the generic parameters of the delegated method are forwarded
+    /// like by default, but `Vec<T>` is passed in place of its parameter `T`.
+    Mapping(Vec<(syn::Ident, syn::GenericArgument)>),
+}
+
+impl syn::parse::Parse for GenericsForwarding {
+    fn parse(input: ParseStream) -> Result<Self, Error> {
+        if input.is_empty() {
+            return Ok(Self::Infer);
+        }
+        let fork = input.fork();
+        if let Ok(ident) = fork.parse::<syn::Ident>() {
+            if ident == "infer" && fork.is_empty() {
+                input.parse::<syn::Ident>()?;
+                return Ok(Self::Infer);
+            }
+        }
+
+        let arguments =
+            syn::punctuated::Punctuated::<syn::GenericArgument, Token![,]>::parse_terminated(
+                input,
+            )?;
+        let mut mapping = vec![];
+        for argument in &arguments {
+            match argument {
+                syn::GenericArgument::AssocType(binding) => mapping.push((
+                    binding.ident.clone(),
+                    syn::GenericArgument::Type(binding.ty.clone()),
+                )),
+                syn::GenericArgument::AssocConst(binding) => mapping.push((
+                    binding.ident.clone(),
+                    syn::GenericArgument::Const(binding.value.clone()),
+                )),
+                syn::GenericArgument::Constraint(constraint) => {
+                    return Err(Error::new(
+                        constraint.ident.span(),
+                        "Cannot use a trait bound in the `generics` attribute",
+                    ));
+                }
+                _ => {}
+            }
+        }
+        if mapping.is_empty() {
+            Ok(Self::Explicit(arguments))
+        } else if mapping.len() == arguments.len() {
+            Ok(Self::Mapping(mapping))
+        } else {
+            Err(Error::new_spanned(
+                arguments,
+                "Cannot mix positional (`A`) and mapped (`T = A`) arguments in the `generics` attribute",
+            ))
+        }
     }
 }
 
@@ -233,22 +999,213 @@ pub enum ReturnExpression {
 
 pub enum TargetSpecifier {
     Field(GetFieldAttribute),
+    FieldUpdate(FieldUpdateAttribute),
     Method(CallMethodAttribute),
+    Function(CallFunctionAttribute),
+    FieldCall(CallFieldAttribute),
 }
 
 impl TargetSpecifier {
-    pub fn get_member(&self, default: &syn::Ident) -> syn::Member {
+    pub fn get_member(&self, default: &syn::Ident) -> MemberPath {
         match self {
             Self::Field(GetFieldAttribute {
                 member: Some(member),
                 ..
             }) => member.clone(),
-            Self::Field(_) => default.clone().into(),
-            Self::Method(method) => method.name.clone().into(),
+            Self::FieldUpdate(FieldUpdateAttribute {
+                member: Some(member),
+                ..
+            }) => member.clone(),
+            Self::FieldCall(CallFieldAttribute {
+                member: Some(member),
+                ..
+            }) => member.clone(),
+            Self::FieldUpdate(FieldUpdateAttribute { kind, member: None }) => {
+                let name = default.to_string();
+                match name.strip_prefix(kind.method_prefix()) {
+                    Some(field) if !field.is_empty() => {
+                        syn::Ident::new(field, default.span()).into()
+                    }
+                    _ => default.clone().into(),
+                }
+            }
+            Self::Method(CallMethodAttribute {
+                name: Some(name), ..
+            }) => name.clone().into(),
+            Self::Function(CallFunctionAttribute { path, .. }) => match path.path.segments.last() {
+                Some(segment) => segment.ident.clone().into(),
+                None => default.clone().into(),
+            },
+            Self::Field(_) | Self::Method(_) | Self::FieldCall(_) => default.clone().into(),
+        }
+    }
+}
+
+/// Kinds of segment attributes that a method can refuse to inherit with `#[no_inherit(...)]`.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum InheritedAttribute {
+    Into,
+    TryInto,
+    Unwrap,
+    Await,
+    Through,
+    Expr,
+    Field,
+    Generics,
+    Newtype,
+    Chain,
+    Update,
+    Rewrap,
+    Fallback,
+    Lock,
+    Guard,
+}
+
+impl InheritedAttribute {
+    const ALL: [Self; 15] = [
+        Self::Into,
+        Self::TryInto,
+        Self::Unwrap,
+        Self::Await,
+        Self::Through,
+        Self::Expr,
+        Self::Field,
+        Self::Generics,
+        Self::Newtype,
+        Self::Chain,
+        Self::Update,
+        Self::Rewrap,
+        Self::Fallback,
+        Self::Lock,
+        Self::Guard,
+    ];
+
+    fn from_path(path: &Path) -> Option<Self> {
+        let ident = path.get_ident()?.to_string();
+        let kind = match ident.as_str() {
+            "into" => Self::Into,
+            "try_into" => Self::TryInto,
+            "unwrap" => Self::Unwrap,
+            "await" => Self::Await,
+            "through" => Self::Through,
+            "expr" => Self::Expr,
+            "field" => Self::Field,
+            "generics" => Self::Generics,
+            "newtype" => Self::Newtype,
+            "chain" => Self::Chain,
+            "update" => Self::Update,
+            "rewrap" => Self::Rewrap,
+            "fallback" => Self::Fallback,
+            "lock" => Self::Lock,
+            "guard" => Self::Guard,
+            _ => return None,
+        };
+        Some(kind)
+    }
+
+    fn of_expression(expression: &ReturnExpression) -> Self {
+        match expression {
+            ReturnExpression::Into(_) => Self::Into,
+            ReturnExpression::TryInto => Self::TryInto,
+            ReturnExpression::Unwrap => Self::Unwrap,
+        }
+    }
+}
+
+/// Describes how a pass-through (non-delegate) segment attribute is merged into a method.
+#[derive(Clone, Copy)]
+enum MergeMode {
+    /// The segment attribute is always added to the method.
+    Append,
+    /// The segment attribute is added only if the method does not have an attribute with the same path.
+    Override,
+    /// The segment attribute is not added to the method.
+    Skip,
+}
+
+impl MergeMode {
+    fn from_ident(ident: &syn::Ident) -> Option<Self> {
+        let mode = match ident.to_string().as_str() {
+            "append" => Self::Append,
+            "override" => Self::Override,
+            "skip" => Self::Skip,
+            _ => return None,
+        };
+        Some(mode)
+    }
+}
+
+fn path_key(path: &Path) -> String {
+    path.segments
+        .iter()
+        .map(|segment| segment.ident.to_string())
+        .collect::<Vec<_>>()
+        .join("::")
+}
+
+/// Decides how are pass-through segment attributes merged into methods, based on their path.
+#[derive(Default, Clone)]
+pub struct MergePolicy {
+    modes: Vec<(String, MergeMode)>,
+}
+
+impl MergePolicy {
+    /// Returns true if the segment attribute `attribute` should be added to an item that already
+    /// has the attributes `existing`.
+    fn inherits<'a>(
+        &self,
+        attribute: &Attribute,
+        mut existing: impl Iterator<Item = &'a Attribute>,
+    ) -> bool {
+        match self.mode(attribute.path()) {
+            MergeMode::Append => true,
+            MergeMode::Override => {
+                let key = path_key(attribute.path());
+                !existing.any(|attr| path_key(attr.path()) == key)
+            }
+            MergeMode::Skip => false,
+        }
+    }
+
+    fn mode(&self, path: &Path) -> MergeMode {
+        let key = path_key(path);
+        if let Some((_, mode)) = self.modes.iter().rev().find(|(path, _)| *path == key) {
+            return *mode;
+        }
+        match key.as_str() {
+            // Documentation is concatenated, and lints and `cfg`s compose.
+            "doc" | "cfg" | "cfg_attr" | "allow" | "warn" | "deny" | "forbid" | "expect" => {
+                MergeMode::Append
+            }
+            // Attributes like `inline`, `must_use` or `deprecated` can be used only once.
+            _ => MergeMode::Override,
         }
     }
 }
 
+impl syn::parse::Parse for MergePolicy {
+    fn parse(input: ParseStream) -> Result<Self, Error> {
+        use syn::ext::IdentExt;
+
+        let mut modes = vec![];
+        while !input.is_empty() {
+            // `override` is a reserved keyword, so it cannot be parsed as a regular path
+            let name = input.call(syn::Ident::parse_any)?;
+            let mode = MergeMode::from_ident(&name).ok_or_else(|| {
+                Error::new(name.span(), "Expected `append`, `override` or `skip`")
+            })?;
+            let content;
+            syn::parenthesized!(content in input);
+            let paths = syn::punctuated::Punctuated::<Path, Token![,]>::parse_terminated(&content)?;
+            modes.extend(paths.iter().map(|path| (path_key(path), mode)));
+            if !input.is_empty() {
+                input.parse::<Token![,]>()?;
+            }
+        }
+        Ok(MergePolicy { modes })
+    }
+}
+
 enum ParsedAttribute {
     ReturnExpression(ReturnExpression),
     Await(bool),
@@ -256,6 +1213,16 @@ enum ParsedAttribute {
     ThroughTrait(TraitTarget),
     ConstantAccess(AssociatedConstant),
     Expr(TemplateExpr),
+    NoInherit(Vec<InheritedAttribute>),
+    MergePolicy(MergePolicy),
+    Generics(GenericsForwarding),
+    Newtype,
+    Chain,
+    Update,
+    Rewrap,
+    Fallback(Fallback),
+    Lock(LockAttribute),
+    Guard(GuardMap),
 }
 
 fn parse_attributes(
@@ -275,12 +1242,34 @@ fn parse_attributes(
                     .unwrap_or_default();
                 match name.as_str() {
                     "call" => {
-                        let target = attribute
-                            .parse_args::<CallMethodAttribute>()
-                            .expect("Cannot parse `call` attribute");
+                        let target = if let syn::Meta::Path(_) = &attribute.meta {
+                            CallMethodAttribute::default()
+                        } else {
+                            attribute
+                                .parse_args::<CallMethodAttribute>()
+                                .expect("Cannot parse `call` attribute")
+                        };
                         let spec = TargetSpecifier::Method(target);
                         Some(ParsedAttribute::TargetSpecifier(spec))
                     }
+                    "call_fn" => {
+                        let target = attribute
+                            .parse_args::<CallFunctionAttribute>()
+                            .expect("Cannot parse `call_fn` attribute");
+                        let spec = TargetSpecifier::Function(target);
+                        Some(ParsedAttribute::TargetSpecifier(spec))
+                    }
+                    "call_field" => {
+                        let target = if let syn::Meta::Path(_) = &attribute.meta {
+                            CallFieldAttribute::default()
+                        } else {
+                            attribute
+                                .parse_args::<CallFieldAttribute>()
+                                .expect("Cannot parse `call_field` attribute")
+                        };
+                        let spec = TargetSpecifier::FieldCall(target);
+                        Some(ParsedAttribute::TargetSpecifier(spec))
+                    }
                     "field" => {
                         let target = if let syn::Meta::Path(_) = &attribute.meta {
                             GetFieldAttribute::default()
@@ -292,6 +1281,17 @@ fn parse_attributes(
                         let spec = TargetSpecifier::Field(target);
                         Some(ParsedAttribute::TargetSpecifier(spec))
                     }
+                    "field_set" | "field_replace" | "field_take" => {
+                        let kind = match name.as_str() {
+                            "field_set" => FieldUpdateKind::Set,
+                            "field_replace" => FieldUpdateKind::Replace,
+                            _ => FieldUpdateKind::Take,
+                        };
+                        let spec = TargetSpecifier::FieldUpdate(FieldUpdateAttribute::parse(
+                            attribute, kind,
+                        ));
+                        Some(ParsedAttribute::TargetSpecifier(spec))
+                    }
                     "into" => {
                         let into = match &attribute.meta {
                             Meta::NameValue(_) => {
@@ -342,6 +1342,84 @@ fn parse_attributes(
                             .parse_args::<TemplateExpr>()
                             .expect("Cannot parse `expr` attribute"),
                     )),
+                    "generics" => Some(ParsedAttribute::Generics(
+                        attribute
+                            .parse_args::<GenericsForwarding>()
+                            .expect("Cannot parse `generics` attribute"),
+                    )),
+                    "newtype" => {
+                        if !matches!(attribute.meta, Meta::Path(_)) {
+                            panic!("The `newtype` attribute does not accept any arguments");
+                        }
+                        Some(ParsedAttribute::Newtype)
+                    }
+                    "chain" => {
+                        if !matches!(attribute.meta, Meta::Path(_)) {
+                            panic!("The `chain` attribute does not accept any arguments");
+                        }
+                        Some(ParsedAttribute::Chain)
+                    }
+                    "update" => {
+                        if !matches!(attribute.meta, Meta::Path(_)) {
+                            panic!("The `update` attribute does not accept any arguments");
+                        }
+                        Some(ParsedAttribute::Update)
+                    }
+                    "rewrap" => {
+                        if !matches!(attribute.meta, Meta::Path(_)) {
+                            panic!("The `rewrap` attribute does not accept any arguments");
+                        }
+                        Some(ParsedAttribute::Rewrap)
+                    }
+                    "fallback" => Some(ParsedAttribute::Fallback(
+                        attribute
+                            .parse_args::<Fallback>()
+                            .expect("Cannot parse `fallback` attribute"),
+                    )),
+                    "lock" => Some(ParsedAttribute::Lock(
+                        attribute
+                            .parse_args::<LockAttribute>()
+                            .expect("Cannot parse `lock` attribute"),
+                    )),
+                    "guard" => {
+                        let map = match &attribute.meta {
+                            Meta::Path(_) => None,
+                            _ => Some(
+                                attribute
+                                    .parse_args::<syn::ExprPath>()
+                                    .expect("Cannot parse `guard` attribute"),
+                            ),
+                        };
+                        Some(ParsedAttribute::Guard(GuardMap { map }))
+                    }
+                    "merge_attrs" => Some(ParsedAttribute::MergePolicy(
+                        attribute
+                            .parse_args::<MergePolicy>()
+                            .expect("Cannot parse `merge_attrs` attribute"),
+                    )),
+                    "no_unwrap" => Some(ParsedAttribute::NoInherit(vec![
+                        InheritedAttribute::Unwrap,
+                    ])),
+                    "no_inherit" => {
+                        let kinds = if let Meta::List(meta) = &attribute.meta {
+                            let mut kinds = vec![];
+                            meta.parse_nested_meta(|meta| {
+                                let kind =
+                                    InheritedAttribute::from_path(&meta.path).ok_or_else(|| {
+                                        meta.error(
+                                            "Expected one of `into`, `try_into`, `unwrap`, `await`, `through`, `expr`, `field`, `generics`, `newtype`, `chain`, `update`, `rewrap`, `fallback`, `lock` or `guard`",
+                                        )
+                                    })?;
+                                kinds.push(kind);
+                                Ok(())
+                            })
+                            .expect("Invalid `no_inherit` arguments");
+                            kinds
+                        } else {
+                            InheritedAttribute::ALL.to_vec()
+                        };
+                        Some(ParsedAttribute::NoInherit(kinds))
+                    }
                     _ => None,
                 }
             } else {
@@ -362,19 +1440,43 @@ pub struct MethodAttributes<'a> {
     pub target_specifier: Option<TargetSpecifier>,
     pub expressions: VecDeque<ReturnExpression>,
     pub generate_await: Option<bool>,
-    pub target_trait: Option<TypePath>,
+    pub target_trait: Option<TraitTarget>,
     pub associated_constant: Option<AssociatedConstant>,
     pub expr_attr: Option<TemplateExpr>,
+    pub generics: Option<GenericsForwarding>,
+    pub newtype: bool,
+    pub chain: bool,
+    pub update: bool,
+    pub rewrap: bool,
+    pub fallback: Option<Fallback>,
+    pub lock: Option<LockAttribute>,
+    pub guard: Option<GuardMap>,
+    no_inherit: Vec<InheritedAttribute>,
 }
 
 /// Iterates through the attributes of a method and filters special attributes.
-/// - call => sets the name of the target method to call
+/// - call => sets the name of the target method to call (`#[call]` calls a method with the same name),
+///   optionally with generic arguments (`#[call(parse::<u32>)]`) or as a path to a function that
+///   receives the delegated expression as its first argument (`#[call(<Inner as Trait>::method)]`)
+/// - call_fn => calls a free function with (a reference to) the delegated expression as its first argument
+/// - call_field => calls a closure or function pointer stored in a field of the delegated expression
+/// - field => returns a field of the delegated expression instead of calling a method
+/// - field_set/field_replace/field_take => assigns, replaces or takes a field of the delegated expression
 /// - into => generates a `into()` call after the delegated expression
 /// - try_into => generates a `try_into()` call after the delegated expression
 /// - await => generates an `.await` expression after the delegated expression
 /// - unwrap => generates a `unwrap()` call after the delegated expression
 /// - through => generates a UFCS call (`Target::method(&<expr>, ...)`) around the delegated expression
 /// - const => generates a getter to a trait associated constant
+/// - generics => selects the generic arguments passed to the delegated method
+/// - newtype => unwraps `Self`-typed parameters using the delegator
+/// - chain => returns `self` (or rebuilds `Self`) instead of the result of the delegated method
+/// - update => rebuilds `Self` around the result of a consuming delegated method
+/// - rewrap => wraps the result of each arm of a `match` delegator into its variant
+/// - fallback => selects the result of the method when the target of a `to?` segment is missing
+/// - lock => locks or borrows the delegator (`Mutex`, `RwLock`, `RefCell`) before calling the method
+/// - guard => maps the guard of the delegator to the reference returned by the delegated method
+/// - no_unwrap/no_inherit => prevents the method from inheriting (some) attributes of its segment
 pub fn parse_method_attributes<'a>(
     attrs: &'a [Attribute],
     method: &syn::TraitItemFn,
@@ -385,6 +1487,15 @@ pub fn parse_method_attributes<'a>(
     let mut target_trait: Option<TraitTarget> = None;
     let mut associated_constant: Option<AssociatedConstant> = None;
     let mut expr_attr: Option<TemplateExpr> = None;
+    let mut generics: Option<GenericsForwarding> = None;
+    let mut newtype = false;
+    let mut chain = false;
+    let mut update = false;
+    let mut rewrap = false;
+    let mut fallback: Option<Fallback> = None;
+    let mut lock: Option<LockAttribute> = None;
+    let mut guard: Option<GuardMap> = None;
+    let mut no_inherit: Vec<InheritedAttribute> = vec![];
 
     let (parsed, other) = parse_attributes(attrs);
     for attr in parsed {
@@ -435,11 +1546,55 @@ pub fn parse_method_attributes<'a>(
                 }
                 expr_attr = Some(token_tree);
             }
+            ParsedAttribute::Generics(forwarding) => {
+                if generics.is_some() {
+                    panic!(
+                        "Multiple generics attributes specified for {}",
+                        method.sig.ident
+                    )
+                }
+                generics = Some(forwarding);
+            }
+            ParsedAttribute::Newtype => newtype = true,
+            ParsedAttribute::Chain => chain = true,
+            ParsedAttribute::Update => update = true,
+            ParsedAttribute::Rewrap => rewrap = true,
+            ParsedAttribute::Fallback(value) => {
+                if fallback.is_some() {
+                    panic!(
+                        "Multiple fallback attributes specified for {}",
+                        method.sig.ident
+                    )
+                }
+                fallback = Some(value);
+            }
+            ParsedAttribute::Lock(value) => {
+                if lock.is_some() {
+                    panic!(
+                        "Multiple lock attributes specified for {}",
+                        method.sig.ident
+                    )
+                }
+                lock = Some(value);
+            }
+            ParsedAttribute::Guard(value) => {
+                if guard.is_some() {
+                    panic!(
+                        "Multiple guard attributes specified for {}",
+                        method.sig.ident
+                    )
+                }
+                guard = Some(value);
+            }
+            ParsedAttribute::NoInherit(kinds) => no_inherit.extend(kinds),
+            ParsedAttribute::MergePolicy(_) => {
+                panic!("`merge_attrs` attribute can only be specified on a segment.");
+            }
         }
     }
 
     if associated_constant.is_some() && target_spec.is_some() {
-        panic!("Cannot use both `call`/`field` and `const` attributes.");
+        panic!("Cannot use both `call`/`call_fn`/`call_field`/`field` and `const` attributes.");
     }
 
     MethodAttributes {
@@ -447,25 +1602,188 @@ pub fn parse_method_attributes<'a>(
         target_specifier: target_spec,
         generate_await,
         expressions: expressions.into(),
-        target_trait: target_trait.map(|t| t.type_path),
+        target_trait,
         associated_constant,
         expr_attr,
+        generics,
+        newtype,
+        chain,
+        update,
+        rewrap,
+        fallback,
+        lock,
+        guard,
+        no_inherit,
+    }
+}
+
+/// Attributes of an arm of a `match` delegator, which override the attributes of the method
+/// for that arm.
+#[derive(Default)]
+pub struct ArmAttributes {
+    pub target_specifier: Option<TargetSpecifier>,
+    /// Return modifiers of the arm, which replace the return modifiers of the method.
+    pub expressions: Option<VecDeque<ReturnExpression>>,
+    /// `#[skip]`: the body of the arm is used as the result, without calling the delegated method.
+    pub skip: bool,
+}
+
+impl ArmAttributes {
+    pub fn is_empty(&self) -> bool {
+        self.target_specifier.is_none() && self.expressions.is_none() && !self.skip
     }
 }
 
+/// Parses the attributes of a `match` arm. Returns the delegation attributes of the arm and the
+/// remaining attributes, which are kept on the arm.
+/// - call/call_fn/call_field/field => overrides the target of the method for the arm
+/// - into/try_into/unwrap => replaces the return modifiers of the method for the arm
+/// - skip => uses the body of the arm as the result
+pub fn parse_arm_attributes(attrs: &[Attribute]) -> (ArmAttributes, Vec<Attribute>) {
+    let mut arm_attrs = ArmAttributes::default();
+    let mut expressions: Vec<ReturnExpression> = vec![];
+
+    let (parsed, other) = parse_attributes(attrs);
+    let (skip, other): (Vec<&Attribute>, Vec<&Attribute>) =
+        other.partition(|attribute| attribute.path().is_ident("skip"));
+    arm_attrs.skip = !skip.is_empty();
+
+    for attr in parsed {
+        match attr {
+            ParsedAttribute::ReturnExpression(expr) => expressions.push(expr),
+            ParsedAttribute::TargetSpecifier(TargetSpecifier::FieldUpdate(_)) => {
+                panic!("Field update attributes cannot be specified on a match arm.");
+            }
+            ParsedAttribute::TargetSpecifier(spec) => {
+                if arm_attrs.target_specifier.is_some() {
+                    panic!("Multiple field/call attributes specified for a match arm");
+                }
+                arm_attrs.target_specifier = Some(spec);
+            }
+            _ => panic!(
+                "Only `call`, `call_fn`, `call_field`, `field`, `into`, `try_into`, `unwrap` and `skip` attributes can be specified on a match arm."
+            ),
+        }
+    }
+    if arm_attrs.skip && (arm_attrs.target_specifier.is_some() || !expressions.is_empty()) {
+        panic!("Cannot combine `skip` with other attributes on a match arm.");
+    }
+    if !expressions.is_empty() {
+        arm_attrs.expressions = Some(expressions.into());
+    }
+
+    (arm_attrs, other.into_iter().cloned().collect())
+}
+
+#[derive(Clone)]
 pub struct SegmentAttributes {
     pub expressions: Vec<ReturnExpression>,
+    pub field: Option<GetFieldAttribute>,
     pub generate_await: Option<bool>,
-    pub target_trait: Option<TypePath>,
+    pub target_trait: Option<TraitTarget>,
     pub other_attrs: Vec<Attribute>,
+    pub merge_policy: MergePolicy,
     pub expr_attr: Option<TemplateExpr>,
+    pub generics: Option<GenericsForwarding>,
+    pub newtype: bool,
+    pub chain: bool,
+    pub update: bool,
+    pub rewrap: bool,
+    pub fallback: Option<Fallback>,
+    pub lock: Option<LockAttribute>,
+    pub guard: Option<GuardMap>,
+    no_inherit: Vec<InheritedAttribute>,
+}
+
+impl SegmentAttributes {
+    /// Checks that the segment does not use attributes that only make sense on nested segments.
+    pub fn check_top_level(&self) {
+        if !self.no_inherit.is_empty() {
+            panic!("`no_inherit`/`no_unwrap` attributes can only be specified on methods and nested segments.");
+        }
+    }
+
+    /// Combines the attributes of a nested segment with the attributes of its parent segment.
+    /// The same rules as in [`combine_attributes`] apply, with the nested segment taking the place
+    /// of the method.
+    pub fn inherit_from(mut self, parent: &SegmentAttributes) -> SegmentAttributes {
+        let no_inherit = std::mem::take(&mut self.no_inherit);
+        let inherits = |kind: InheritedAttribute| !no_inherit.contains(&kind);
+
+        let has_into = self
+            .expressions
+            .iter()
+            .any(|expr| matches!(expr, ReturnExpression::Into(_)));
+        let mut expressions: Vec<ReturnExpression> = parent
+            .expressions
+            .iter()
+            .filter(|expr| inherits(InheritedAttribute::of_expression(expr)))
+            .filter(|expr| !(has_into && matches!(expr, ReturnExpression::Into(_))))
+            .cloned()
+            .collect();
+        expressions.append(&mut self.expressions);
+        self.expressions = expressions;
+
+        if self.field.is_none() && inherits(InheritedAttribute::Field) {
+            self.field.clone_from(&parent.field);
+        }
+        if self.generate_await.is_none() && inherits(InheritedAttribute::Await) {
+            self.generate_await = parent.generate_await;
+        }
+        if self.target_trait.is_none() && inherits(InheritedAttribute::Through) {
+            self.target_trait.clone_from(&parent.target_trait);
+        }
+        if self.expr_attr.is_none() && inherits(InheritedAttribute::Expr) {
+            self.expr_attr.clone_from(&parent.expr_attr);
+        }
+        if self.generics.is_none() && inherits(InheritedAttribute::Generics) {
+            self.generics.clone_from(&parent.generics);
+        }
+        self.newtype |= parent.newtype && inherits(InheritedAttribute::Newtype);
+        self.chain |= parent.chain && inherits(InheritedAttribute::Chain);
+        self.update |= parent.update && inherits(InheritedAttribute::Update);
+        self.rewrap |= parent.rewrap && inherits(InheritedAttribute::Rewrap);
+        if self.fallback.is_none() && inherits(InheritedAttribute::Fallback) {
+            self.fallback.clone_from(&parent.fallback);
+        }
+        if self.lock.is_none() && inherits(InheritedAttribute::Lock) {
+            self.lock.clone_from(&parent.lock);
+        }
+        if self.guard.is_none() && inherits(InheritedAttribute::Guard) {
+            self.guard.clone_from(&parent.guard);
+        }
+
+        let mut merge_policy = parent.merge_policy.clone();
+        merge_policy.modes.append(&mut self.merge_policy.modes);
+        self.merge_policy = merge_policy;
+
+        let inherited: Vec<Attribute> = parent
+            .other_attrs
+            .iter()
+            .filter(|attr| self.merge_policy.inherits(attr, self.other_attrs.iter()))
+            .cloned()
+            .collect();
+        self.other_attrs.extend(inherited);
+        self
+    }
 }
 
 pub fn parse_segment_attributes(attrs: &[Attribute]) -> SegmentAttributes {
     let mut expressions: Vec<ReturnExpression> = vec![];
+    let mut field: Option<GetFieldAttribute> = None;
     let mut generate_await: Option<bool> = None;
     let mut target_trait: Option<TraitTarget> = None;
     let mut expr_attr: Option<TemplateExpr> = None;
+    let mut merge_policy = MergePolicy::default();
+    let mut generics: Option<GenericsForwarding> = None;
+    let mut newtype = false;
+    let mut chain = false;
+    let mut update = false;
+    let mut rewrap = false;
+    let mut fallback: Option<Fallback> = None;
+    let mut lock: Option<LockAttribute> = None;
+    let mut guard: Option<GuardMap> = None;
+    let mut no_inherit: Vec<InheritedAttribute> = vec![];
 
     let (parsed, other) = parse_attributes(attrs);
 
@@ -484,8 +1802,24 @@ pub fn parse_segment_attributes(attrs: &[Attribute]) -> SegmentAttributes {
                 }
                 target_trait = Some(target);
             }
-            ParsedAttribute::TargetSpecifier(_) => {
-                panic!("Field/call attribute cannot be specified on a `to <expr>` segment.");
+            ParsedAttribute::TargetSpecifier(TargetSpecifier::Field(target)) => {
+                if target.member.is_some() {
+                    panic!("Field attribute on a `to <expr>` segment cannot name a field, use e.g. `#[field]`, `#[field(&)]` or `#[field(clone)]`.");
+                }
+                if field.is_some() {
+                    panic!("Multiple `field` attributes specified for segment");
+                }
+                field = Some(target);
+            }
+            ParsedAttribute::TargetSpecifier(TargetSpecifier::FieldUpdate(_)) => {
+                panic!("Field update attributes cannot be specified on a `to <expr>` segment.");
+            }
+            ParsedAttribute::TargetSpecifier(
+                TargetSpecifier::Method(_)
+                | TargetSpecifier::Function(_)
+                | TargetSpecifier::FieldCall(_),
+            ) => {
+                panic!("Call attribute cannot be specified on a `to <expr>` segment.");
             }
             ParsedAttribute::ConstantAccess(_) => {
                 panic!("Const attribute cannot be specified on a `to <expr>` segment.");
@@ -496,65 +1830,155 @@ pub fn parse_segment_attributes(attrs: &[Attribute]) -> SegmentAttributes {
                 }
                 expr_attr = Some(token_tree);
             }
+            ParsedAttribute::Generics(forwarding) => {
+                if generics.is_some() {
+                    panic!("Multiple `generics` attributes specified for segment");
+                }
+                generics = Some(forwarding);
+            }
+            ParsedAttribute::Newtype => newtype = true,
+            ParsedAttribute::Chain => chain = true,
+            ParsedAttribute::Update => update = true,
+            ParsedAttribute::Rewrap => rewrap = true,
+            ParsedAttribute::Fallback(value) => {
+                if fallback.is_some() {
+                    panic!("Multiple `fallback` attributes specified for segment");
+                }
+                fallback = Some(value);
+            }
+            ParsedAttribute::Lock(value) => {
+                if lock.is_some() {
+                    panic!("Multiple `lock` attributes specified for segment");
+                }
+                lock = Some(value);
+            }
+            ParsedAttribute::Guard(value) => {
+                if guard.is_some() {
+                    panic!("Multiple `guard` attributes specified for segment");
+                }
+                guard = Some(value);
+            }
+            ParsedAttribute::NoInherit(kinds) => no_inherit.extend(kinds),
+            ParsedAttribute::MergePolicy(policy) => merge_policy.modes.extend(policy.modes),
         }
     }
     SegmentAttributes {
         expressions,
+        field,
         generate_await,
-        target_trait: target_trait.map(|t| t.type_path),
+        target_trait,
         other_attrs: other.cloned().collect::<Vec<_>>(),
+        merge_policy,
         expr_attr,
+        generics,
+        newtype,
+        chain,
+        update,
+        rewrap,
+        fallback,
+        lock,
+        guard,
+        no_inherit,
     }
 }
 
 /// Applies default values from the segment and adds them to the method attributes.
+///
+/// Attributes that can only be specified once (`await`, `through`, `expr`, `field`, `generics`,
+/// `fallback`, `lock`, `guard`) are inherited
+/// from the segment only if the method does not specify them.
+/// Return expressions of the segment are applied first, in the order in which they are written,
+/// followed by the return expressions of the method. A segment `into` is skipped if the method
+/// has its own `into`.
+/// Methods can opt out of inheriting any of these with `#[no_inherit(...)]` or `#[no_unwrap]`.
+///
+/// Other (pass-through) segment attributes are merged according to the `merge_attrs` policy of
+/// the segment. By default, `doc`, `cfg`, `cfg_attr` and lint attributes are appended to the
+/// attributes of the method, and all other attributes are only added if the method does not
+/// have an attribute with the same path.
 pub fn combine_attributes<'a>(
     mut method_attrs: MethodAttributes<'a>,
     segment_attrs: &'a SegmentAttributes,
 ) -> MethodAttributes<'a> {
     let SegmentAttributes {
         expressions,
+        field,
         generate_await,
         target_trait,
         other_attrs,
+        merge_policy,
         expr_attr,
+        generics,
+        newtype,
+        chain,
+        update,
+        rewrap,
+        fallback,
+        lock,
+        guard,
+        // Already applied when the segment inherited attributes from its parent segment
+        no_inherit: _,
     } = segment_attrs;
+    let no_inherit = std::mem::take(&mut method_attrs.no_inherit);
+    let inherits = |kind: InheritedAttribute| !no_inherit.contains(&kind);
 
-    if method_attrs.generate_await.is_none() {
+    if method_attrs.generate_await.is_none() && inherits(InheritedAttribute::Await) {
         method_attrs.generate_await = *generate_await;
     }
 
-    if method_attrs.target_trait.is_none() {
+    if method_attrs.target_specifier.is_none()
+        && method_attrs.associated_constant.is_none()
+        && inherits(InheritedAttribute::Field)
+    {
+        method_attrs.target_specifier = field.clone().map(TargetSpecifier::Field);
+    }
+
+    if method_attrs.target_trait.is_none() && inherits(InheritedAttribute::Through) {
         method_attrs.target_trait.clone_from(target_trait);
     }
 
-    if method_attrs.expr_attr.is_none() {
+    if method_attrs.expr_attr.is_none() && inherits(InheritedAttribute::Expr) {
         method_attrs.expr_attr.clone_from(expr_attr);
     }
 
-    for expr in expressions {
-        match expr {
-            ReturnExpression::Into(path) => {
-                if !method_attrs
-                    .expressions
-                    .iter()
-                    .any(|expr| matches!(expr, ReturnExpression::Into(_)))
-                {
-                    method_attrs
-                        .expressions
-                        .push_front(ReturnExpression::Into(path.clone()));
-                }
-            }
-            _ => method_attrs.expressions.push_front(expr.clone()),
-        }
+    if method_attrs.generics.is_none() && inherits(InheritedAttribute::Generics) {
+        method_attrs.generics.clone_from(generics);
+    }
+
+    method_attrs.newtype |= *newtype && inherits(InheritedAttribute::Newtype);
+    method_attrs.chain |= *chain && inherits(InheritedAttribute::Chain);
+    method_attrs.update |= *update && inherits(InheritedAttribute::Update);
+    method_attrs.rewrap |= *rewrap && inherits(InheritedAttribute::Rewrap);
+    if method_attrs.fallback.is_none() && inherits(InheritedAttribute::Fallback) {
+        method_attrs.fallback.clone_from(fallback);
+    }
+    if method_attrs.lock.is_none() && inherits(InheritedAttribute::Lock) {
+        method_attrs.lock.clone_from(lock);
+    }
+    if method_attrs.guard.is_none() && inherits(InheritedAttribute::Guard) {
+        method_attrs.guard.clone_from(guard);
+    }
+    let return_modes = [method_attrs.chain, method_attrs.update, method_attrs.rewrap];
+    if return_modes.iter().filter(|enabled| **enabled).count() > 1 {
+        panic!("Use at most one of the `chain`, `update` and `rewrap` attributes.");
+    }
+
+    let has_into = method_attrs
+        .expressions
+        .iter()
+        .any(|expr| matches!(expr, ReturnExpression::Into(_)));
+    let inherited: Vec<ReturnExpression> = expressions
+        .iter()
+        .filter(|expr| inherits(InheritedAttribute::of_expression(expr)))
+        .filter(|expr| !(has_into && matches!(expr, ReturnExpression::Into(_))))
+        .cloned()
+        .collect();
+    for expr in inherited.into_iter().rev() {
+        method_attrs.expressions.push_front(expr);
     }
 
     for other_attr in other_attrs {
-        if !method_attrs
-            .attributes
-            .iter()
-            .any(|attr| attr.path().get_ident() == other_attr.path().get_ident())
-        {
+        if merge_policy.inherits(other_attr, method_attrs.attributes.iter().copied()) {
             method_attrs.attributes.push(other_attr);
         }
     }
@@ -18,6 +18,81 @@
 //!     }
 //! }
 //! ```
+//! - Call a method with explicit generic arguments, or a function given by its path, which receives
+//!   the delegated expression as its first argument
+//! ```rust
+//! use delegate::delegate;
+//!
+//! trait Describe {
+//!     fn describe(&self) -> String;
+//! }
+//!
+//! struct Inner(String);
+//! impl Describe for Inner {
+//!     fn describe(&self) -> String { self.0.clone() }
+//! }
+//!
+//! struct Wrapper { inner: Inner }
+//! impl Wrapper {
+//!     delegate! {
+//!         to self.inner.0 {
+//!             // calls self.inner.0.parse::<u32>()
+//!             #[call(parse::<u32>)]
+//!             pub fn parse(&self) -> Result<u32, std::num::ParseIntError>;
+//!         }
+//!         to &self.inner {
+//!             // calls <Inner as Describe>::describe(&self.inner)
+//!             #[call(<Inner as Describe>::describe)]
+//!             pub fn describe(&self) -> String;
+//!         }
+//!     }
+//! }
+//! ```
+//! - Call a free function with (a reference to) the delegated expression as its first argument
+//! ```rust
+//! use delegate::delegate;
+//!
+//! struct Buffer { data: Vec<u8> }
+//!
+//! impl Buffer {
+//!     delegate! {
+//!         to self.data {
+//!             // calls std::mem::take(&mut self.data)
+//!             #[call_fn(&mut std::mem::take)]
+//!             pub fn take(&mut self) -> Vec<u8>;
+//!
+//!             // calls String::from_utf8(self.data)
+//!             #[call_fn(String::from_utf8)]
+//!             pub fn into_string(self) -> Result<String, std::string::FromUtf8Error>;
+//!         }
+//!     }
+//! }
+//! ```
+//! - Call closures or function pointers stored in fields (optionally wrapped in an `Option`)
+//! ```rust
+//! use delegate::delegate;
+//!
+//! struct Callbacks {
+//!     on_event: Box<dyn Fn(u32) -> bool>,
+//!     on_close: Option<Box<dyn FnMut()>>,
+//! }
+//!
+//! struct Plugin { callbacks: Callbacks }
+//!
+//! impl Plugin {
+//!     delegate! {
+//!         to self.callbacks {
+//!             // calls (self.callbacks.on_event)(event)
+//!             #[call_field]
+//!             pub fn on_event(&self, event: u32) -> bool;
+//!
+//!             // calls self.callbacks.on_close.as_mut().map(|f| f())
+//!             #[call_field(optional on_close)]
+//!             pub fn close(&mut self) -> Option<()>;
+//!         }
+//!     }
+//! }
+//! ```
 //! - Use an arbitrary inner field expression
 //! ```rust
 //! use delegate::delegate;
@@ -36,6 +111,128 @@
 //! }
 //! ```
 //!
+//! - Select the delegator based on the receiver of each method with `to receiver { ... }`
+//! ```rust
+//! use delegate::delegate;
+//!
+//! use std::rc::Rc;
+//! use std::cell::RefCell;
+//!
+//! struct Wrapper { inner: Rc<RefCell<Vec<u32>>> }
+//! impl Wrapper {
+//!     delegate! {
+//!         to receiver {
+//!             &self => self.inner.borrow(),
+//!             &mut self => self.inner.borrow_mut(),
+//!             self => self.inner.take(),
+//!         } {
+//!             pub fn len(&self) -> usize;
+//!             pub fn push(&mut self, val: u32);
+//!             pub fn into_iter(self) -> std::vec::IntoIter<u32>;
+//!         }
+//!     }
+//! }
+//! ```
+//!
+//! - Lock or borrow `Mutex`, `RwLock` and `RefCell` delegators with `#[lock(mutex | rwlock | refcell)]`.
+//!   The locking method (`lock`, `read`/`write`, `borrow`/`borrow_mut`) is chosen based on the
+//!   receiver of the method. Lock errors panic by default, or can be returned to the caller with
+//!   `try` (uses the `try_*` methods) or `err(<expr>)`. `await` awaits async locks.
+//! ```rust
+//! use delegate::delegate;
+//!
+//! use std::rc::Rc;
+//! use std::cell::RefCell;
+//! use std::sync::Mutex;
+//!
+//! struct Wrapper { inner: Rc<RefCell<Vec<u32>>>, log: Mutex<Vec<String>> }
+//! impl Wrapper {
+//!     delegate! {
+//!         #[lock(refcell)]
+//!         to self.inner {
+//!             // self.inner.borrow_mut().push(val)
+//!             pub fn push(&mut self, val: u32);
+//!             // self.inner.borrow().len()
+//!             pub fn len(&self) -> usize;
+//!         }
+//!         // match self.log.lock() { Ok(guard) => Ok(guard.is_empty()), Err(_) => Err(()) }
+//!         #[lock(mutex, err(()))]
+//!         to self.log {
+//!             pub fn is_empty(&self) -> Result<bool, ()>;
+//!         }
+//!     }
+//! }
+//! ```
+//!
+//! - Return references from inside `RefCell`s and locks by mapping the guard with `#[guard]`
+//! ```rust
+//! use delegate::delegate;
+//!
+//! use std::cell::{Ref, RefCell};
+//!
+//! struct Wrapper { inner: RefCell<Vec<u32>> }
+//! impl Wrapper {
+//!     delegate! {
+//!         #[lock(refcell)]
+//!         to self.inner {
+//!             // Ref::map(self.inner.borrow(), |target| target.as_slice())
+//!             #[guard]
+//!             pub fn as_slice(&self) -> Ref<'_, [u32]>;
+//!         }
+//!     }
+//! }
+//! ```
+//!
+//! - Delegate to targets that can only be accessed in a closure with `to with <function> |<parameter>|`
+//! ```rust
+//! use delegate::delegate;
+//!
+//! use std::cell::Cell;
+//!
+//! thread_local! {
+//!     static COUNTER: Cell<u32> = Cell::new(0);
+//! }
+//!
+//! struct Counter;
+//! impl Counter {
+//!     delegate! {
+//!         to with COUNTER.with |counter| {
+//!             // COUNTER.with(|counter| counter.get())
+//!             pub fn get() -> u32;
+//!             // COUNTER.with(|counter| counter.set(value))
+//!             pub fn set(value: u32);
+//!         }
+//!     }
+//! }
+//! ```
+//!
+//! - Delegate to optional targets (`Option<T>`, `Option<Box<dyn Trait>>`, `self.weak.upgrade()`)
+//!   with `to? <expr>`. The `#[fallback(none | default | err(<expr>) | panic | panic("message"))]`
+//!   attribute selects what is returned when the target is missing (`None` by default, nothing for
+//!   methods without a return value).
+//! ```rust
+//! use delegate::delegate;
+//!
+//! struct Inner;
+//! impl Inner {
+//!     fn len(&self) -> usize { 1 }
+//! }
+//!
+//! struct Wrapper { inner: Option<Inner> }
+//! impl Wrapper {
+//!     delegate! {
+//!         to? self.inner {
+//!             // `Some(inner.len())` or `None`
+//!             pub fn len(&self) -> Option<usize>;
+//!
+//!             #[call(len)]
+//!             #[fallback(err("missing inner"))]
+//!             pub fn try_len(&self) -> Result<usize, &'static str>;
+//!         }
+//!     }
+//! }
+//! ```
+//!
 //! - Delegate to enum variants
 //!
 //! ```rust
@@ -86,8 +283,8 @@
 //!         // ```rust
 //!         // match self {
 //!         //     Enum::A(a) => a.dbg_inner(),
-//!         //     Enum::B(b) => { println!("i am b"); b }.dbg_inner(),
-//!         //     Enum::C { v: c } => { c }.dbg_inner(),
+//!         //     Enum::B(b) => { println!("i am b"); b.dbg_inner() },
+//!         //     Enum::C { v: c } => { c.dbg_inner() },
 //!         // }
 //!         // ```
 //!         to match self {
@@ -100,6 +297,107 @@
 //!     }
 //! }
 //! ```
+//! - Besides `match`, the delegated call is also applied to each branch of `if`/`else` chains and
+//!   to the final expression of blocks (e.g. after a `let ... else`), including nested ones,
+//!   so each branch can delegate to a different type.
+//! ```rust
+//! use delegate::delegate;
+//!
+//! struct Cache {
+//!     cache: Option<Vec<u8>>,
+//!     fallback: String,
+//! }
+//! impl Cache {
+//!     delegate! {
+//!         // if let Some(cache) = &self.cache { cache.len() } else { (&self.fallback).len() }
+//!         to if let Some(cache) = &self.cache { cache } else { &self.fallback } {
+//!             fn len(&self) -> usize;
+//!         }
+//!     }
+//! }
+//! ```
+//! - Arms of the `match` can override the delegation with their own attributes: `#[call(...)]`
+//!   (or another target attribute), return modifiers (which replace those of the method) and
+//!   `#[skip]`, which uses the body of the arm as the result.
+//! ```rust
+//! use delegate::delegate;
+//!
+//! enum Collection {
+//!     Items(Vec<u8>),
+//!     Text(String),
+//!     Empty,
+//! }
+//! impl Collection {
+//!     delegate! {
+//!         to match self {
+//!             Collection::Items(items) => items,
+//!             #[call(count)]
+//!             Collection::Text(text) => text.chars(),
+//!             #[skip]
+//!             Collection::Empty => 0,
+//!         } {
+//!             // match self {
+//!             //     Collection::Items(items) => items.len(),
+//!             //     Collection::Text(text) => text.chars().count(),
+//!             //     Collection::Empty => 0,
+//!             // }
+//!             fn len(&self) -> usize;
+//!         }
+//!     }
+//! }
+//! ```
+//! - The `variants(...)` shorthand expands to such a `match` on `self`. `Enum::A` matches a tuple variant
+//!   with a single field, `Enum::B { field }` matches a field of a struct variant (add `, ..` to ignore
+//!   its other fields). The variants have to be paths with the enum (`Enum::A` or `Self::A`), otherwise
+//!   `variants(...)` calls a function named `variants`.
+//! ```rust
+//! use delegate::delegate;
+//!
+//! enum Enum {
+//!     A(String),
+//!     B { text: String },
+//!     C { text: String, count: usize },
+//! }
+//! impl Enum {
+//!     delegate! {
+//!         // match self {
+//!         //     Enum::A(inner) => inner,
+//!         //     Enum::B { text: inner } => inner,
+//!         //     Enum::C { text: inner, .. } => inner,
+//!         // }
+//!         to variants(Enum::A, Enum::B { text }, Enum::C { text, .. }) {
+//!             fn len(&self) -> usize;
+//!         }
+//!     }
+//! }
+//! ```
+//! - With `#[rewrap]`, the result of each arm is wrapped back into the variant of the arm. Methods
+//!   returning `Option<Self>` or `Result<Self, E>` map the variant over the result.
+//! ```rust
+//! use delegate::delegate;
+//!
+//! struct Meters(f64);
+//! impl Meters {
+//!     fn abs(&self) -> Self { Meters(self.0.abs()) }
+//! }
+//!
+//! enum Length {
+//!     Meters(Meters),
+//! }
+//! impl Length {
+//!     delegate! {
+//!         #[rewrap]
+//!         to match self {
+//!             Length::Meters(m) => m,
+//!         } {
+//!             // match self {
+//!             //     Length::Meters(m) => Length::Meters(m.abs()),
+//!             // }
+//!             fn abs(&self) -> Self;
+//!         }
+//!     }
+//! }
+//! ```
 //!
 //! - Use modifiers that alter the generated method body
 //! ```rust
@@ -145,6 +443,61 @@
 //! }
 //! ```
 //!
+//! - Delegate builder methods with `#[chain]`. The result of the delegated method is discarded and
+//!   `self` is returned instead. Methods taking `self` by value rebuild `Self` around the returned
+//!   inner value (`Self { inner: self.inner.method(...), ..self }`), or rebuild the variant of
+//!   each arm of a `match` delegator.
+//! ```rust
+//! use delegate::delegate;
+//!
+//! #[derive(Default)]
+//! struct InnerBuilder { size: u32 }
+//! impl InnerBuilder {
+//!     fn size(&mut self, size: u32) -> &mut Self { self.size = size; self }
+//! }
+//!
+//! struct Builder { inner: InnerBuilder }
+//! impl Builder {
+//!     delegate! {
+//!         #[chain]
+//!         to self.inner {
+//!             // Calls `self.inner.size(size)` and returns `self`
+//!             pub fn size(&mut self, size: u32) -> &mut Self;
+//!         }
+//!     }
+//! }
+//! ```
+//!
+//! - Delegate consuming methods with `#[update]`. The delegated field is moved out of `self`, and
+//!   `Self` is rebuilt around the result of the delegated method, keeping the other fields. With a
+//!   `match` delegator, each arm rebuilds its variant (the arm has to return one of its bindings).
+//! ```rust
+//! use delegate::delegate;
+//!
+//! struct Inner { retries: u32 }
+//! impl Inner {
+//!     fn with_retries(self, retries: u32) -> Self { Self { retries } }
+//! }
+//!
+//! enum Client {
+//!     Local(Inner),
+//!     Remote { address: String, inner: Inner },
+//! }
+//! impl Client {
+//!     delegate! {
+//!         #[update]
+//!         to match self {
+//!             Client::Local(inner) => inner,
+//!             Client::Remote { address, inner } => inner,
+//!         } {
+//!             // Rebuilds `Client::Local(inner.with_retries(retries))` or
+//!             // `Client::Remote { address, inner: inner.with_retries(retries) }`
+//!             pub fn with_retries(self, retries: u32) -> Self;
+//!         }
+//!     }
+//! }
+//! ```
+//!
 //! - Custom called expression
 //!
 //! The `#[expr()]` attribute can be used to modify the delegated call. You can use the `$` sigil as a placeholder for what delegate would normally expand to, and wrap that expression with custom code.
@@ -246,6 +599,100 @@
 //!   }
 //! }
 //! ```
+//! - Segment return modifiers are applied first, in the order in which they are written, followed by
+//!   the modifiers of the method. A method can opt out of inherited segment attributes with
+//!   `#[no_unwrap]`, `#[no_inherit]` (ignores all of them) or `#[no_inherit(...)]` with a list of
+//!   `into`, `try_into`, `unwrap`, `await`, `through`, `expr`, `field`, `generics`, `newtype`, `chain`, `update`,
+//!   `rewrap`, `fallback`, `lock` and `guard`:
+//! ```rust
+//! use delegate::delegate;
+//!
+//! struct Inner;
+//! impl Inner {
+//!     fn foo(&self) -> Result<u32, ()> { Ok(0) }
+//! }
+//!
+//! struct Wrapper { inner: Inner }
+//!
+//! impl Wrapper {
+//!     delegate! {
+//!         #[unwrap]
+//!         #[expr($ + 1)]
+//!         to self.inner {
+//!             fn foo(&self) -> u32; // calls self.inner.foo().unwrap() + 1
+//!
+//!             #[no_unwrap]
+//!             #[no_inherit(expr)]
+//!             #[call(foo)]
+//!             fn try_foo(&self) -> Result<u32, ()>; // calls self.inner.foo()
+//!         }
+//!     }
+//! }
+//! ```
+//! - Other segment attributes are copied to all methods of the segment. `doc`, `cfg`, `cfg_attr` and
+//!   lint attributes are appended to the attributes of the method, other attributes are only copied
+//!   if the method does not have an attribute with the same path. This can be changed per attribute
+//!   with `#[merge_attrs(append(...), override(...), skip(...))]`:
+//! ```rust
+//! use delegate::delegate;
+//!
+//! struct Inner;
+//! impl Inner {
+//!     fn foo(&self) -> u32 { 0 }
+//! }
+//!
+//! struct Wrapper { inner: Inner }
+//!
+//! impl Wrapper {
+//!     delegate! {
+//!         /// Delegated to `Inner`.
+//!         #[allow(unused)]
+//!         #[merge_attrs(skip(doc), override(allow))]
+//!         to self.inner {
+//!             /// Only this doc comment and `#[allow(dead_code)]` end up on `foo`.
+//!             #[allow(dead_code)]
+//!             fn foo(&self) -> u32;
+//!         }
+//!     }
+//! }
+//! ```
+//! - Nest segments. A nested `{ ... }` group inherits the delegator and attributes of its parent
+//!   segment, `to .suffix { ... }` also extends the delegator of the parent and `to <expr> { ... }`
+//!   replaces it:
+//! ```rust
+//! use delegate::delegate;
+//!
+//! struct Sub;
+//! impl Sub {
+//!     fn len(&self) -> Result<usize, ()> { Ok(0) }
+//! }
+//!
+//! struct Inner { sub: Sub }
+//! impl Inner {
+//!     fn parse(&self, value: &str) -> Result<u8, std::num::ParseIntError> { value.parse() }
+//! }
+//!
+//! struct Wrapper { inner: Inner }
+//!
+//! impl Wrapper {
+//!     delegate! {
+//!         #[unwrap]
+//!         to self.inner {
+//!             fn parse(&self, value: &str) -> u8; // calls self.inner.parse(value).unwrap()
+//!
+//!             #[into]
+//!             {
+//!                 #[call(parse)]
+//!                 fn parse_u32(&self, value: &str) -> u32; // calls self.inner.parse(value).unwrap().into()
+//!             }
+//!
+//!             to .sub {
+//!                 fn len(&self) -> usize; // calls self.inner.sub.len().unwrap()
+//!             }
+//!         }
+//!     }
+//! }
+//! ```
 //! - Specify expressions in the signature that will be used as delegated arguments
 //! ```rust
 //! use delegate::delegate;
@@ -301,6 +748,25 @@
 //!     }
 //! }
 //! ```
+//! - The `#[newtype]` method or segment attribute unwraps all `Self`, `&Self`, `&mut Self`,
+//!   `Option<Self>` and `Vec<Self>` parameters into the inner value, by projecting them the same
+//!   way as the delegator projects `self` (e.g. `other` becomes `&other.0` for `to self.0`).
+//! ```rust
+//! use delegate::delegate;
+//!
+//! struct Wrapper(Vec<u32>);
+//! impl Wrapper {
+//!     delegate! {
+//!         #[newtype]
+//!         to self.0 {
+//!             // Calls `self.0.append(&mut other.0)`
+//!             pub fn append(&mut self, other: &mut Self);
+//!             // Calls `self.0.eq(&other.0)`
+//!             pub fn eq(&self, other: &Self) -> bool;
+//!         }
+//!     }
+//! }
+//! ```
 //! - Specify a trait through which will the delegated method be called
 //!   (using [UFCS](https://doc.rust-lang.org/reference/expressions/call-expr.html#disambiguating-function-calls).
 //! ```rust
@@ -329,11 +795,52 @@
 //!     }
 //! }
 //! ```
+//!   Generic traits are called through a qualified path (`<_ as Trait<A>>::method`). Associated type
+//!   bindings (`Iterator<Item = u32>`) are ignored and lifetimes bound with `for<'a>` are elided.
+//! ```rust
+//! use delegate::delegate;
 //!
-//! - Add additional arguments to method
+//! struct Wrapper(Vec<u32>);
+//! impl Wrapper {
+//!     delegate! {
+//!         to &self.0 {
+//!             // Calls `<_ as std::ops::Index<usize>>::index(&self.0, index)`
+//!             #[through(std::ops::Index<usize>)]
+//!             pub fn index(&self, index: usize) -> &u32;
+//!         }
+//!     }
+//! }
+//! ```
 //!
-//!  ```rust
-//!  use delegate::delegate;
+//! - Select the generic arguments passed to the delegated method. By default, type and const
+//!   parameters are forwarded as a turbofish, unless the method has `impl Trait` parameters.
+//!   `#[generics(infer)]` passes no generic arguments, `#[generics(B, Vec<A>)]` passes the listed
+//!   ones positionally and `#[generics(T = Vec<T>)]` forwards the parameters of the delegated method
+//!   with `Vec<T>` in place of `T`.
+//! ```rust
+//! use delegate::delegate;
+//!
+//! struct Inner;
+//! impl Inner {
+//!     fn pair<A: Default, B: Default>(&self) -> (A, B) { (A::default(), B::default()) }
+//! }
+//!
+//! struct Wrapper(Inner);
+//! impl Wrapper {
+//!     delegate! {
+//!         to self.0 {
+//!             // Calls `self.0.pair::<Y, X>()`
+//!             #[generics(Y, X)]
+//!             pub fn pair<X: Default, Y: Default>(&self) -> (Y, X);
+//!         }
+//!     }
+//! }
+//! ```
+//!
+//! - Add additional arguments to method
+//!
+//!  ```rust
+//!  use delegate::delegate;
 //!  use std::cell::OnceCell;
 //!  struct Inner(u32);
 //!  impl Inner {
@@ -390,6 +897,32 @@
 //!
 //!   assert_eq!(B::foo(1), 2);
 //!   ```
+//!
+//!   Associated functions of a trait can be delegated using a qualified type as the target
+//!   (`to <Inner as Trait>`), or with the `#[through]` attribute:
+//!   ```rust
+//!   use delegate::delegate;
+//!
+//!   #[derive(Default)]
+//!   struct Inner;
+//!
+//!   struct Wrapper;
+//!
+//!   impl Wrapper {
+//!       delegate! {
+//!           to <Inner as Default> {
+//!               // calls <Inner as Default>::default()
+//!               fn default() -> Inner;
+//!           }
+//!           to Inner {
+//!               // calls <Inner as Default>::default()
+//!               #[through(Default)]
+//!               #[call(default)]
+//!               fn create() -> Inner;
+//!           }
+//!       }
+//!   }
+//!   ```
 //! - Delegate associated constants
 //!
 //! ```rust
@@ -472,6 +1005,154 @@
 //!     }
 //! }
 //! ```
+//!
+//! You can also put `#[field]`, `#[field(&)]` or `#[field(&mut)]` on a whole segment, which turns every
+//! method in it into a field accessor. Individual methods can still use `#[field(...)]` with a different
+//! field or reference, or opt back into a method call with `#[call]` or `#[call(name)]`.
+//! ```rust
+//! use delegate::delegate;
+//!
+//! struct Config {
+//!     name: String,
+//!     retries: u32,
+//! }
+//!
+//! impl Config {
+//!     fn describe(&self) -> String {
+//!         format!("{} ({})", self.name, self.retries)
+//!     }
+//! }
+//!
+//! struct Service {
+//!     config: Config,
+//! }
+//!
+//! impl Service {
+//!     delegate! {
+//!         #[field(&)]
+//!         to self.config {
+//!             /// Expands to `&self.config.name`
+//!             fn name(&self) -> &String;
+//!
+//!             /// Expands to `&self.config.retries`
+//!             fn retries(&self) -> &u32;
+//!
+//!             /// Expands to `self.config.describe()`
+//!             #[call]
+//!             fn describe(&self) -> String;
+//!         }
+//!     }
+//! }
+//! ```
+//!
+//! A getter mode can be put in front of the field name to change how the field is returned:
+//! `#[field(clone)]`, `#[field(copy)]`, `#[field(as_ref)]`, `#[field(as_deref)]` and `#[field(as_mut)]`.
+//! A mode keyword alone on a method with the same name, after `&`, in a field path (`clone.max`) or
+//! written as a raw identifier (`r#clone`) is treated as a field name.
+//! ```rust
+//! use delegate::delegate;
+//! use std::sync::Arc;
+//!
+//! struct Inner {
+//!     name: String,
+//!     nickname: Option<String>,
+//!     parent: Option<u32>,
+//!     shared: Arc<u32>,
+//! }
+//!
+//! struct Wrapper {
+//!     inner: Inner,
+//! }
+//!
+//! impl Wrapper {
+//!     delegate! {
+//!         to self.inner {
+//!             /// Expands to `Clone::clone(&self.inner.shared)`
+//!             #[field(clone)]
+//!             fn shared(&self) -> Arc<u32>;
+//!
+//!             /// Expands to `self.inner.name.as_ref()`
+//!             #[field(as_ref)]
+//!             fn name(&self) -> &str;
+//!
+//!             /// Expands to `self.inner.nickname.as_deref()`
+//!             #[field(as_deref)]
+//!             fn nickname(&self) -> Option<&str>;
+//!
+//!             /// Expands to `self.inner.parent.as_mut()`
+//!             #[field(as_mut parent)]
+//!             fn parent_mut(&mut self) -> Option<&mut u32>;
+//!         }
+//!     }
+//! }
+//! ```
+//!
+//! The field can also be a path of nested named and unnamed fields, e.g. `#[field(&config.limits.0.max)]`.
+//! ```rust
+//! use delegate::delegate;
+//!
+//! struct Limits {
+//!     max: u32,
+//!     range: (u32, u32),
+//! }
+//!
+//! struct Config {
+//!     limits: Limits,
+//! }
+//!
+//! struct Service {
+//!     config: Config,
+//! }
+//!
+//! impl Service {
+//!     delegate! {
+//!         to self.config {
+//!             /// Expands to `&self.config.limits.max`
+//!             #[field(&limits.max)]
+//!             fn max(&self) -> &u32;
+//!
+//!             /// Expands to `self.config.limits.range.1`
+//!             #[field(limits.range.1)]
+//!             fn end(&self) -> u32;
+//!         }
+//!     }
+//! }
+//! ```
+//!
+//! Fields can also be updated with `#[field_set]`, `#[field_replace]` and `#[field_take]`. Without an explicit
+//! field name, the `set_`, `replace_` or `take_` prefix is stripped from the method name to get the field name.
+//! ```rust
+//! use delegate::delegate;
+//!
+//! struct Datum {
+//!     value: u32,
+//!     label: String,
+//! }
+//!
+//! struct DatumWrapper(Datum);
+//!
+//! impl DatumWrapper {
+//!     delegate! {
+//!         to self.0 {
+//!             /// Expands to `self.0.value = value;`
+//!             #[field_set]
+//!             fn set_value(&mut self, value: u32);
+//!
+//!             /// Expands to `self.0.label = label.into();`
+//!             #[field_set(label)]
+//!             fn rename(&mut self, #[into] label: &str);
+//!
+//!             /// Expands to `std::mem::replace(&mut self.0.label, label)`
+//!             #[field_replace]
+//!             fn replace_label(&mut self, label: String) -> String;
+//!
+//!             /// Expands to `std::mem::take(&mut self.0.label)`
+//!             #[field_take]
+//!             fn take_label(&mut self) -> String;
+//!         }
+//!     }
+//! }
+//! ```
 
 extern crate proc_macro;
 use std::mem;
@@ -481,13 +1162,15 @@ use proc_macro::TokenStream;
 
 use proc_macro2::Ident;
 use quote::{quote, ToTokens};
+use syn::parse::discouraged::Speculative;
 use syn::parse::{Parse, ParseStream};
 use syn::spanned::Spanned;
 use syn::visit_mut::VisitMut;
 use syn::{parse_quote, Error, Expr, ExprField, ExprMethodCall, FnArg, GenericParam, Meta};
 
 use crate::attributes::{
-    combine_attributes, parse_method_attributes, parse_segment_attributes, ReturnExpression,
+    combine_attributes, parse_arm_attributes, parse_method_attributes, parse_segment_attributes,
+    ArmAttributes, Fallback, FieldUpdateKind, GenericsForwarding, LockAttribute, ReturnExpression,
     SegmentAttributes, TargetSpecifier,
 };
 
@@ -496,6 +1179,68 @@ mod attributes;
 mod kw {
     syn::custom_keyword!(to);
     syn::custom_keyword!(target);
+    syn::custom_keyword!(variants);
+    syn::custom_keyword!(receiver);
+    syn::custom_keyword!(with);
+}
+
+/// Describes how does a delegated method take `self`.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum ReceiverKind {
+    /// `&self`
+    Shared,
+    /// `&mut self`
+    Mutable,
+    /// `self` (or `self: Box<Self>` and similar)
+    Owned,
+    /// Associated function without a receiver
+    None,
+}
+
+impl ReceiverKind {
+    /// Parses the receiver of an entry of a `to receiver { ... }` delegator
+    /// (`&self`, `&mut self`, `self` or `Self` for associated functions).
+    fn parse_pattern(input: ParseStream) -> Result<Self, Error> {
+        if input.parse::<Option<syn::Token![Self]>>()?.is_some() {
+            return Ok(Self::None);
+        }
+        let kind = if input.parse::<Option<syn::Token![&]>>()?.is_some() {
+            if input.parse::<Option<syn::Token![mut]>>()?.is_some() {
+                Self::Mutable
+            } else {
+                Self::Shared
+            }
+        } else {
+            Self::Owned
+        };
+        input.parse::<syn::Token![self]>()?;
+        Ok(kind)
+    }
+
+    fn pattern(self) -> &'static str {
+        match self {
+            Self::Shared => "&self",
+            Self::Mutable => "&mut self",
+            Self::Owned => "self",
+            Self::None => "Self",
+        }
+    }
+
+    fn of(signature: &syn::Signature) -> Self {
+        match signature.receiver() {
+            Some(receiver) => match &*receiver.ty {
+                syn::Type::Reference(reference) if reference.mutability.is_some() => Self::Mutable,
+                syn::Type::Reference(_) => Self::Shared,
+                _ => Self::Owned,
+            },
+            None => Self::None,
+        }
+    }
+
+    /// Returns true if a `#[lock]` locks the delegator for writing (`write`, `borrow_mut`).
+    fn locks_mutably(self) -> bool {
+        matches!(self, Self::Mutable | Self::Owned)
+    }
 }
 
 #[derive(Clone)]
@@ -804,42 +1549,422 @@ impl syn::parse::Parse for DelegatedMethod {
     }
 }
 
+/// Delegator expression(s) of a segment.
+#[derive(Clone)]
+enum Delegator {
+    /// `to <expr>`: the same expression is used for all methods.
+    Single(syn::Expr),
+    /// `to receiver { &self => <expr>, &mut self => <expr>, self => <expr>, Self => <expr> }`:
+    /// the expression is selected based on the receiver of each method.
+    PerReceiver(Vec<(ReceiverKind, syn::Expr)>),
+}
+
+impl Delegator {
+    /// Parses the delegator of a segment, which is either a `receiver { ... }` list or a single
+    /// delegator expression.
+    fn parse(input: ParseStream) -> Result<Self, Error> {
+        if input.peek(kw::receiver) && input.peek2(syn::token::Brace) {
+            // `receiver { ... }` could also be a variable followed by the methods of the segment
+            let fork = input.fork();
+            if Self::parse_receivers(&fork).is_ok() && fork.peek(syn::token::Brace) {
+                return Self::parse_receivers(input);
+            }
+        }
+        parse_delegator(input).map(Self::Single)
+    }
+
+    fn parse_receivers(input: ParseStream) -> Result<Self, Error> {
+        input.parse::<kw::receiver>()?;
+        let content;
+        syn::braced!(content in input);
+
+        let mut delegators: Vec<(ReceiverKind, syn::Expr)> = vec![];
+        while !content.is_empty() {
+            let mut receivers = vec![ReceiverKind::parse_pattern(&content)?];
+            while content.parse::<Option<syn::Token![|]>>()?.is_some() {
+                receivers.push(ReceiverKind::parse_pattern(&content)?);
+            }
+            content.parse::<syn::Token![=>]>()?;
+            let expr = parse_delegator(&content)?;
+            for receiver in receivers {
+                if delegators.iter().any(|(kind, _)| *kind == receiver) {
+                    return Err(content.error(format!(
+                        "Multiple delegators specified for `{}`",
+                        receiver.pattern()
+                    )));
+                }
+                delegators.push((receiver, expr.clone()));
+            }
+            if content.is_empty() {
+                break;
+            }
+            content.parse::<syn::Token![,]>()?;
+        }
+        if delegators.is_empty() {
+            return Err(content.error("Expected at least one delegator"));
+        }
+        Ok(Self::PerReceiver(delegators))
+    }
+
+    /// Returns the delegator expression for a method with the given receiver.
+    fn for_receiver(&self, receiver: ReceiverKind, method: &Ident) -> &syn::Expr {
+        match self {
+            Self::Single(expr) => expr,
+            Self::PerReceiver(delegators) => delegators
+                .iter()
+                .find(|(kind, _)| *kind == receiver)
+                .map(|(_, expr)| expr)
+                .unwrap_or_else(|| {
+                    panic!(
+                        "No delegator specified for `{}` methods ({})",
+                        receiver.pattern(),
+                        method
+                    )
+                }),
+        }
+    }
+
+    fn try_map(&self, f: impl Fn(&syn::Expr) -> Result<syn::Expr, Error>) -> Result<Self, Error> {
+        match self {
+            Self::Single(expr) => f(expr).map(Self::Single),
+            Self::PerReceiver(delegators) => delegators
+                .iter()
+                .map(|(kind, expr)| Ok((*kind, f(expr)?)))
+                .collect::<Result<_, Error>>()
+                .map(Self::PerReceiver),
+        }
+    }
+}
+
+/// `to with <function> |<parameter>| { ... }`: the target is only accessible through a closure
+/// passed to a scoped-access function (like `LocalKey::with`), which receives it as its parameter.
+#[derive(Clone)]
+struct Scope {
+    function: syn::Expr,
+    parameter: Ident,
+    /// `|<parameter>|?`: the result of the function is propagated with `?` and wrapped in `Ok`.
+    propagate: bool,
+}
+
+impl Scope {
+    /// Parses a scoped delegator, which is used as the delegator of the segment.
+    fn parse(input: ParseStream) -> Result<(Delegator, Self), Error> {
+        input.parse::<kw::with>()?;
+        let mut function = proc_macro2::TokenStream::new();
+        while !input.peek(syn::Token![|]) {
+            if input.is_empty() || input.peek(syn::token::Brace) {
+                return Err(input.error("Expected `|<parameter>|` after the scoped function"));
+            }
+            function.extend(std::iter::once(input.parse::<proc_macro2::TokenTree>()?));
+        }
+        let function: syn::Expr = syn::parse2(function)?;
+        input.parse::<syn::Token![|]>()?;
+        let parameter: Ident = input.parse()?;
+        input.parse::<syn::Token![|]>()?;
+        let propagate = input.parse::<Option<syn::Token![?]>>()?.is_some();
+
+        let delegator = Delegator::Single(parse_quote!(#parameter));
+        Ok((
+            delegator,
+            Scope {
+                function,
+                parameter,
+                propagate,
+            },
+        ))
+    }
+
+    /// Wraps the body of a method into a call of the scoped function.
+    fn wrap(&self, body: &proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+        let Scope {
+            function,
+            parameter,
+            propagate,
+        } = self;
+        let function = postfix_base(function);
+        if *propagate {
+            quote! { ::core::result::Result::Ok(#function(|#parameter| #body)?) }
+        } else {
+            quote! { #function(|#parameter| #body) }
+        }
+    }
+}
+
+/// Parses the target of a segment after `to` (and `?`): a delegator, optionally accessed
+/// through a scoped function (`with <function> |<parameter>|`).
+fn parse_target(input: ParseStream) -> Result<(Delegator, Option<Scope>), Error> {
+    if input.peek(kw::with) {
+        // `with` could also be a variable, only use the scope if it is valid
+        let fork = input.fork();
+        if Scope::parse(&fork).is_ok() && fork.peek(syn::token::Brace) {
+            let (delegator, scope) = Scope::parse(input)?;
+            return Ok((delegator, Some(scope)));
+        }
+    }
+    Ok((Delegator::parse(input)?, None))
+}
+
 struct DelegatedSegment {
-    delegator: syn::Expr,
+    delegator: Delegator,
+    /// `to? <expr>`: the delegator is an `Option`, which might not contain the target.
+    optional: bool,
+    scope: Option<Scope>,
     methods: Vec<DelegatedMethod>,
     segment_attrs: SegmentAttributes,
 }
 
-impl syn::parse::Parse for DelegatedSegment {
+/// A variant of the `variants(...)` delegator shorthand.
+/// `Enum::A` matches a tuple variant with a single field, `Enum::B { field }` matches a field
+/// of a struct variant (`Enum::B { field, .. }` ignores the other fields).
+struct VariantTarget {
+    path: syn::Path,
+    field: Option<(syn::Member, Option<syn::Token![..]>)>,
+}
+
+impl syn::parse::Parse for VariantTarget {
     fn parse(input: ParseStream) -> Result<Self, Error> {
+        let path: syn::Path = input.parse()?;
+        let field = if input.peek(syn::token::Brace) {
+            let content;
+            syn::braced!(content in input);
+            let member: syn::Member = content.parse()?;
+            let rest = if content.parse::<Option<syn::Token![,]>>()?.is_some() {
+                content.parse()?
+            } else {
+                None
+            };
+            if !content.is_empty() {
+                return Err(
+                    content.error("Expected a single field name, optionally followed by `, ..`")
+                );
+            }
+            Some((member, rest))
+        } else {
+            None
+        };
+        Ok(Self { path, field })
+    }
+}
+
+/// Expands `variants(Enum::A, Enum::B { field })` into
+/// `match self { Enum::A(inner) => inner, Enum::B { field: inner } => inner }`.
+fn expand_variants(variants: &[VariantTarget]) -> syn::Expr {
+    let binding = hygienic_ident("inner");
+    let arms = variants.iter().map(|variant| {
+        let path = &variant.path;
+        match &variant.field {
+            Some((member, rest)) => quote! { #path { #member: #binding, #rest } => #binding, },
+            None => quote! { #path(#binding) => #binding, },
+        }
+    });
+    parse_quote! {
+        match self {
+            #(#arms)*
+        }
+    }
+}
+
+/// Parses the `variants(Enum::A, Enum::B { field })` delegator shorthand.
+fn parse_variants(input: ParseStream) -> Result<Vec<VariantTarget>, Error> {
+    input.parse::<kw::variants>()?;
+    let content;
+    syn::parenthesized!(content in input);
+    let variants = content.parse_terminated(VariantTarget::parse, syn::Token![,])?;
+    if variants.is_empty() {
+        return Err(content.error("Expected at least one variant"));
+    }
+    Ok(variants.into_iter().collect())
+}
+
+/// Parses the delegator expression of a segment.
+/// Besides expressions, it also accepts qualified types (`<Inner as Trait>` or `<Vec<u32>>`),
+/// which can be used to delegate associated functions, and the `variants(...)` shorthand.
+fn parse_delegator(input: ParseStream) -> Result<syn::Expr, Error> {
+    if input.peek(kw::variants) && input.peek2(syn::token::Paren) {
+        // `variants(...)` could also be a function call, only use it if all arguments are
+        // enum variant paths (`Enum::A`, `Self::B { field }`)
+        let fork = input.fork();
+        if let Ok(variants) = parse_variants(&fork) {
+            if fork.peek(syn::token::Brace)
+                && variants
+                    .iter()
+                    .all(|variant| variant.path.segments.len() > 1)
+            {
+                input.advance_to(&fork);
+                return Ok(expand_variants(&variants));
+            }
+        }
+    }
+    if input.peek(syn::Token![<]) {
+        // `<Ty as Trait>::f(self)` is a regular expression, only use the qualified type itself
+        // as the delegator if it is directly followed by the body of the segment
+        let fork = input.fork();
+        if let Ok(qself) = parse_qualified_type(&fork) {
+            if fork.peek(syn::token::Brace) {
+                input.advance_to(&fork);
+                return Ok(qself);
+            }
+        }
+    }
+    syn::Expr::parse_without_eager_brace(input)
+}
+
+/// Parses a qualified type delegator (`<Ty>` or `<Ty as Trait>`).
+fn parse_qualified_type(input: ParseStream) -> Result<syn::Expr, Error> {
+    let lt: syn::Token![<] = input.parse()?;
+    let ty: syn::Type = input.parse()?;
+    let trait_path = if input.peek(syn::Token![as]) {
+        let as_token: syn::Token![as] = input.parse()?;
+        let path: syn::Path = input.parse()?;
+        Some((as_token, path))
+    } else {
+        None
+    };
+    let gt: syn::Token![>] = input.parse()?;
+    let (as_token, path) = trait_path.unzip();
+    Ok(Expr::Verbatim(quote! { #lt #ty #as_token #path #gt }))
+}
+
+/// Target of a nested segment, relative to the delegator of its parent segment.
+enum NestedTarget {
+    /// `{ ... }`: uses the delegator of the parent segment.
+    Inherit,
+    /// `to .field.method() { ... }`: appends the tokens to the delegator of the parent segment.
+    Extend(proc_macro2::TokenStream),
+    /// `to <expr> { ... }`: uses a different delegator.
+    Replace(Delegator),
+}
+
+/// Creates an identifier for a binding introduced by the generated code.
+/// It uses mixed-site hygiene, so that it cannot collide with (or shadow) the parameters of the
+/// delegated method or identifiers used in the delegator.
+fn hygienic_ident(name: &str) -> Ident {
+    Ident::new(name, proc_macro2::Span::mixed_site())
+}
+
+/// Returns the tokens of an expression that can be followed by a postfix operation
+/// (`.field`, `.method()`), wrapping it in parentheses if needed.
+fn postfix_base(expr: &syn::Expr) -> proc_macro2::TokenStream {
+    match expr {
+        Expr::Path(_)
+        | Expr::Field(_)
+        | Expr::MethodCall(_)
+        | Expr::Call(_)
+        | Expr::Index(_)
+        | Expr::Paren(_) => expr.to_token_stream(),
+        _ => quote! { (#expr) },
+    }
+}
+
+impl NestedTarget {
+    fn resolve(self, parent: &Delegator) -> Result<Delegator, Error> {
+        match self {
+            Self::Inherit => Ok(parent.clone()),
+            Self::Extend(suffix) => parent.try_map(|parent| {
+                if let Expr::Match(_) | Expr::Closure(_) = parent {
+                    return Err(Error::new(
+                        suffix.span(),
+                        "A nested segment cannot extend a `match` or closure delegator",
+                    ));
+                }
+                let base = postfix_base(parent);
+                syn::parse2(quote! { #base #suffix })
+            }),
+            Self::Replace(delegator) => Ok(delegator),
+        }
+    }
+}
+
+impl DelegatedSegment {
+    /// Parses a top-level segment (`to <expr> { ... }`) together with all of its nested segments.
+    fn parse_all(input: ParseStream) -> Result<Vec<Self>, Error> {
         let attributes = input.call(tolerant_outer_attributes)?;
         let segment_attrs = parse_segment_attributes(&attributes);
+        segment_attrs.check_top_level();
 
         if let Ok(keyword) = input.parse::<kw::target>() {
             return Err(Error::new(keyword.span(), "You are using the old `target` expression, which is deprecated. Please replace `target` with `to`."));
         } else {
             input.parse::<kw::to>()?;
         }
+        let optional = input.parse::<Option<syn::Token![?]>>()?.is_some();
 
-        syn::Expr::parse_without_eager_brace(input).and_then(|delegator| {
-            let content;
-            syn::braced!(content in input);
+        let (delegator, scope) = parse_target(input)?;
+        Self::parse_body(input, delegator, optional, scope, segment_attrs)
+    }
+
+    /// Parses a nested segment (`{ ... }`, `to .suffix { ... }` or `to <expr> { ... }`),
+    /// which inherits the delegator and attributes of its parent segment.
+    fn parse_nested(input: ParseStream, parent: &Self) -> Result<Vec<Self>, Error> {
+        let attributes = input.call(tolerant_outer_attributes)?;
+        let segment_attrs =
+            parse_segment_attributes(&attributes).inherit_from(&parent.segment_attrs);
+
+        let mut optional = parent.optional;
+        let mut scope = parent.scope.clone();
+        let target = if input.parse::<kw::to>().is_ok() {
+            optional = input.parse::<Option<syn::Token![?]>>()?.is_some();
+            if input.peek(syn::Token![.]) {
+                if parent.optional {
+                    return Err(
+                        input.error("A nested segment cannot extend an optional (`to?`) delegator")
+                    );
+                }
+                let mut suffix = proc_macro2::TokenStream::new();
+                while !input.peek(syn::token::Brace) {
+                    suffix.extend(std::iter::once(input.parse::<proc_macro2::TokenTree>()?));
+                }
+                NestedTarget::Extend(suffix)
+            } else {
+                let (delegator, replaced_scope) = parse_target(input)?;
+                scope = replaced_scope;
+                NestedTarget::Replace(delegator)
+            }
+        } else {
+            NestedTarget::Inherit
+        };
+        let delegator = target.resolve(&parent.delegator)?;
+        Self::parse_body(input, delegator, optional, scope, segment_attrs)
+    }
 
-            let mut methods = vec![];
-            while !content.is_empty() {
-                methods.push(
+    /// Parses the braced list of methods and nested segments of a segment.
+    /// Nested segments are flattened into the returned list, after their parent.
+    fn parse_body(
+        input: ParseStream,
+        delegator: Delegator,
+        optional: bool,
+        scope: Option<Scope>,
+        segment_attrs: SegmentAttributes,
+    ) -> Result<Vec<Self>, Error> {
+        let content;
+        syn::braced!(content in input);
+
+        let mut segment = DelegatedSegment {
+            delegator,
+            optional,
+            scope,
+            methods: vec![],
+            segment_attrs,
+        };
+        let mut nested = vec![];
+        while !content.is_empty() {
+            // Nested segments start with `to` or `{` after their attributes, methods never do.
+            let fork = content.fork();
+            fork.call(tolerant_outer_attributes)?;
+            if fork.peek(kw::to) || fork.peek(syn::token::Brace) {
+                nested.extend(Self::parse_nested(&content, &segment)?);
+            } else {
+                segment.methods.push(
                     content
                         .parse::<DelegatedMethod>()
                         .expect("Cannot parse delegated method"),
                 );
             }
+        }
 
-            Ok(DelegatedSegment {
-                delegator,
-                methods,
-                segment_attrs,
-            })
-        })
+        let mut segments = vec![segment];
+        segments.extend(nested);
+        Ok(segments)
     }
 }
 
@@ -851,7 +1976,7 @@ impl syn::parse::Parse for DelegationBlock {
     fn parse(input: ParseStream) -> Result<Self, Error> {
         let mut segments = vec![];
         while !input.is_empty() {
-            segments.push(input.parse()?);
+            segments.extend(DelegatedSegment::parse_all(input)?);
         }
 
         Ok(DelegationBlock { segments })
@@ -869,12 +1994,522 @@ fn has_inline_attribute(attrs: &[&syn::Attribute]) -> bool {
     })
 }
 
-struct MatchVisitor<F>(F);
+/// Finds `impl Trait` types.
+struct ImplTraitVisitor(bool);
+
+impl VisitMut for ImplTraitVisitor {
+    fn visit_type_impl_trait_mut(&mut self, _: &mut syn::TypeImplTrait) {
+        self.0 = true;
+    }
+}
+
+/// Returns true if any parameter of the signature uses `impl Trait` in its type.
+fn has_impl_trait_parameter(signature: &syn::Signature) -> bool {
+    let mut visitor = ImplTraitVisitor(false);
+    for input in &signature.inputs {
+        if let FnArg::Typed(typed) = input {
+            visitor.visit_type_mut(&mut typed.ty.as_ref().clone());
+        }
+    }
+    visitor.0
+}
+
+/// Kind of a `Self`-typed parameter that is unwrapped by the `#[newtype]` attribute.
+enum SelfParameter {
+    /// `Self`
+    Owned,
+    /// `&Self`
+    Shared,
+    /// `&mut Self`
+    Mutable,
+    /// `Option<Self>`
+    Option,
+    /// `Vec<Self>`
+    Vec,
+}
+
+impl SelfParameter {
+    fn of(ty: &syn::Type) -> Option<Self> {
+        fn is_self(ty: &syn::Type) -> bool {
+            matches!(ty, syn::Type::Path(path) if path.qself.is_none() && path.path.is_ident("Self"))
+        }
+
+        match ty {
+            syn::Type::Reference(reference) if is_self(&reference.elem) => {
+                match reference.mutability {
+                    Some(_) => Some(Self::Mutable),
+                    None => Some(Self::Shared),
+                }
+            }
+            syn::Type::Path(path) if path.qself.is_none() => {
+                if is_self(ty) {
+                    return Some(Self::Owned);
+                }
+                let segment = path.path.segments.last()?;
+                let arguments = match &segment.arguments {
+                    syn::PathArguments::AngleBracketed(arguments) => arguments,
+                    _ => return None,
+                };
+                match arguments.args.first() {
+                    Some(syn::GenericArgument::Type(inner))
+                        if arguments.args.len() == 1 && is_self(inner) =>
+                    {
+                        if segment.ident == "Option" {
+                            Some(Self::Option)
+                        } else if segment.ident == "Vec" {
+                            Some(Self::Vec)
+                        } else {
+                            None
+                        }
+                    }
+                    _ => None,
+                }
+            }
+            syn::Type::Paren(paren) => Self::of(&paren.elem),
+            syn::Type::Group(group) => Self::of(&group.elem),
+            _ => None,
+        }
+    }
+}
+
+/// Replaces `self` with a different expression.
+struct ReplaceSelf<'a>(&'a Expr, bool);
+
+impl VisitMut for ReplaceSelf<'_> {
+    fn visit_expr_mut(&mut self, expr: &mut Expr) {
+        match expr {
+            Expr::Path(path) if path.qself.is_none() && path.path.is_ident("self") => {
+                *expr = self.0.clone();
+                self.1 = true;
+            }
+            // Closures in the delegator might have their own parameters, do not touch them
+            Expr::Closure(_) => {}
+            _ => syn::visit_mut::visit_expr_mut(self, expr),
+        }
+    }
+}
+
+/// Projects `base` (a value of type `Self`) the same way as the delegator projects `self`,
+/// e.g. `other` becomes `other.0` for a `to self.0` segment.
+fn project_self(delegator: &Expr, base: &Expr, method: &Ident) -> Expr {
+    let mut projection = delegator;
+    loop {
+        match projection {
+            Expr::Reference(reference) => projection = &reference.expr,
+            Expr::Paren(paren) => projection = &paren.expr,
+            _ => break,
+        }
+    }
+    if matches!(projection, Expr::Match(_) | Expr::Closure(_)) {
+        panic!(
+            "The `newtype` attribute cannot be used with a `match` or closure delegator ({})",
+            method
+        );
+    }
+
+    let mut projection = projection.clone();
+    let mut visitor = ReplaceSelf(base, false);
+    visitor.visit_expr_mut(&mut projection);
+    if !visitor.1 {
+        panic!(
+            "The `newtype` attribute requires a delegator that uses `self` ({})",
+            method
+        );
+    }
+    projection
+}
+
+/// Unwraps arguments of `Self`-typed parameters (`Self`, `&Self`, `&mut Self`, `Option<Self>` and
+/// `Vec<Self>`) into the inner value, using the projection of the delegator.
+fn unwrap_self_arguments(signature: &syn::Signature, args: &mut [Expr], delegator: &Expr) {
+    for input in &signature.inputs {
+        let (kind, name) = match input {
+            FnArg::Typed(typed) => match (SelfParameter::of(&typed.ty), &*typed.pat) {
+                (Some(kind), syn::Pat::Ident(pat)) => (kind, &pat.ident),
+                _ => continue,
+            },
+            FnArg::Receiver(_) => continue,
+        };
+        // Arguments with a parameter modifier are not plain paths, so they are left alone
+        let argument = match args
+            .iter_mut()
+            .find(|argument| matches!(argument, Expr::Path(path) if path.path.is_ident(name)))
+        {
+            Some(argument) => argument,
+            None => continue,
+        };
 
-impl<F: Fn(&Expr) -> proc_macro2::TokenStream> VisitMut for MatchVisitor<F> {
+        let value = hygienic_ident("value");
+        let value_expr: Expr = parse_quote!(#value);
+        let project = |base: &Expr| project_self(delegator, base, &signature.ident);
+        *argument = match kind {
+            SelfParameter::Owned => project(argument),
+            SelfParameter::Shared => {
+                let projection = project(argument);
+                parse_quote!(&#projection)
+            }
+            SelfParameter::Mutable => {
+                let projection = project(argument);
+                parse_quote!(&mut #projection)
+            }
+            SelfParameter::Option => {
+                let projection = project(&value_expr);
+                parse_quote!(#argument.map(|#value| #projection))
+            }
+            SelfParameter::Vec => {
+                let projection = project(&value_expr);
+                parse_quote! {
+                    ::core::iter::Iterator::collect::<Vec<_>>(
+                        ::core::iter::IntoIterator::into_iter(#argument).map(|#value| #projection)
+                    )
+                }
+            }
+        };
+    }
+}
+
+/// Generates the body of a `#[chain]` method: the result of the delegated method is discarded
+/// and `self` is returned instead. By-value methods rebuild `Self` around the returned inner value.
+fn chain_body(
+    body: &proc_macro2::TokenStream,
+    receiver: ReceiverKind,
+    delegator: &Expr,
+    method: &Ident,
+) -> proc_macro2::TokenStream {
+    match receiver {
+        ReceiverKind::Shared | ReceiverKind::Mutable => quote! {
+            #body;
+            self
+        },
+        ReceiverKind::Owned => rebuild_self(delegator, body, "chain", method),
+        ReceiverKind::None => panic!(
+            "The `chain` attribute can only be used on methods with a receiver ({})",
+            method
+        ),
+    }
+}
+
+/// Generates the body of a method of an optional (`to?`) segment: the delegated method is called
+/// if the delegator contains the target, otherwise the fallback is returned.
+fn optional_body(
+    delegator: &Expr,
+    receiver: ReceiverKind,
+    fallback: &Fallback,
+    method: &Ident,
+    call: impl Fn(&Expr) -> proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let base = postfix_base(delegator);
+    let option = match receiver {
+        ReceiverKind::Shared => quote! { #base.as_ref() },
+        ReceiverKind::Mutable => quote! { #base.as_mut() },
+        ReceiverKind::Owned => base,
+        ReceiverKind::None => panic!(
+            "Optional (`to?`) segments can only delegate methods with a receiver ({})",
+            method
+        ),
+    };
+
+    let target = hygienic_ident("target");
+    let result = call(&parse_quote!(#target));
+    let (result, missing) = match fallback {
+        Fallback::None => (
+            quote! { ::core::option::Option::Some(#result) },
+            quote! { ::core::option::Option::None },
+        ),
+        Fallback::Default => (result, quote! { ::core::default::Default::default() }),
+        Fallback::Err(error) => (
+            quote! { ::core::result::Result::Ok(#result) },
+            quote! { ::core::result::Result::Err(#error) },
+        ),
+        Fallback::Panic(message) => {
+            let message = match message {
+                Some(message) => message.value(),
+                None => format!(
+                    "Cannot delegate `{}`, `{}` is `None`",
+                    method,
+                    delegator.to_token_stream()
+                ),
+            };
+            (result, quote! { ::core::panic!("{}", #message) })
+        }
+    };
+    quote! {
+        match #option {
+            ::core::option::Option::Some(#target) => #result,
+            ::core::option::Option::None => #missing,
+        }
+    }
+}
+
+/// Locks (or borrows) the delegator according to the `lock` attribute of a method.
+/// Returns the guard, or the `Result` of the locking method if the method returns lock errors
+/// to its caller (see [`lock_result_body`]).
+fn lock_delegator(
+    delegator: &Expr,
+    lock: &LockAttribute,
+    receiver: ReceiverKind,
+    is_method: bool,
+    method: &Ident,
+) -> Expr {
+    if !is_method {
+        panic!(
+            "The `lock` attribute can only be used on methods with a receiver ({})",
+            method
+        );
+    }
+    let base = postfix_base(delegator);
+    let name = Ident::new(
+        &lock.method_name(receiver.locks_mutably()),
+        proc_macro2::Span::call_site(),
+    );
+    if lock.returns_result() {
+        parse_quote! { #base.#name() }
+    } else if lock.is_async {
+        parse_quote! { #base.#name().await }
+    } else if lock.kind.is_poisoning() {
+        parse_quote! { #base.#name().unwrap() }
+    } else {
+        parse_quote! { #base.#name() }
+    }
+}
+
+/// Generates the body of a method whose lock returns errors to the caller: the result of the
+/// delegated method is wrapped in `Ok`, and a lock error is returned either as `Err(<error>)` or
+/// converted with `From`.
+fn lock_result_body(
+    locked: &Expr,
+    error: Option<&Expr>,
+    call: impl Fn(&Expr) -> proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let guard = hygienic_ident("guard");
+    let result = call(&parse_quote!(#guard));
+    let failure = match error {
+        Some(error) => quote! {
+            ::core::result::Result::Err(_) => ::core::result::Result::Err(#error)
+        },
+        None => {
+            let error = hygienic_ident("error");
+            quote! {
+                ::core::result::Result::Err(#error) => {
+                    ::core::result::Result::Err(::core::convert::From::from(#error))
+                }
+            }
+        }
+    };
+    quote! {
+        match #locked {
+            // Methods taking `&mut self` need a mutable guard
+            #[allow(unused_mut)]
+            ::core::result::Result::Ok(mut #guard) => ::core::result::Result::Ok(#result),
+            #failure,
+        }
+    }
+}
+
+/// Rebuilds `Self` around a new value of the delegated field (`Self { inner: <body>, ..self }`),
+/// keeping the other fields of `self`. The delegator has to be a field of `self`.
+fn rebuild_self(
+    delegator: &Expr,
+    body: &proc_macro2::TokenStream,
+    attribute: &str,
+    method: &Ident,
+) -> proc_macro2::TokenStream {
+    match delegator {
+        Expr::Field(field)
+            if matches!(&*field.base, Expr::Path(path) if path.path.is_ident("self")) =>
+        {
+            let member = &field.member;
+            quote! { Self { #member: #body, ..self } }
+        }
+        _ => panic!(
+            "The `{}` attribute on a method taking `self` requires a `self.<field>` or `match` delegator ({})",
+            attribute, method
+        ),
+    }
+}
+
+/// Converts a match `pattern` back into the value that it matched, with `binding` replaced by
+/// `value`. `found` is set if the pattern contains the binding.
+fn rebuild_pattern(
+    pattern: &syn::Pat,
+    binding: &Ident,
+    value: &proc_macro2::TokenStream,
+    found: &mut bool,
+) -> proc_macro2::TokenStream {
+    match pattern {
+        syn::Pat::Ident(ident) if ident.subpat.is_none() && ident.by_ref.is_none() => {
+            if ident.ident == *binding {
+                *found = true;
+                value.clone()
+            } else {
+                ident.ident.to_token_stream()
+            }
+        }
+        syn::Pat::TupleStruct(tuple) => {
+            let path = &tuple.path;
+            let elements = tuple
+                .elems
+                .iter()
+                .map(|element| rebuild_pattern(element, binding, value, found));
+            quote! { #path(#(#elements),*) }
+        }
+        syn::Pat::Struct(structure) if structure.rest.is_none() => {
+            let path = &structure.path;
+            let fields = structure.fields.iter().map(|field| {
+                let member = &field.member;
+                let field = rebuild_pattern(&field.pat, binding, value, found);
+                quote! { #member: #field }
+            });
+            quote! { #path { #(#fields),* } }
+        }
+        syn::Pat::Tuple(tuple) => {
+            let elements = tuple
+                .elems
+                .iter()
+                .map(|element| rebuild_pattern(element, binding, value, found));
+            quote! { (#(#elements,)*) }
+        }
+        syn::Pat::Paren(paren) => rebuild_pattern(&paren.pat, binding, value, found),
+        syn::Pat::Lit(_) | syn::Pat::Path(_) => pattern.to_token_stream(),
+        _ => panic!(
+            "Cannot rebuild the value of pattern `{}`, bind all of its fields to variables (without `..` or `ref`)",
+            pattern.to_token_stream()
+        ),
+    }
+}
+
+/// Returns true if the return type is `Option<Self>` or `Result<Self, E>`.
+fn returns_wrapped_self(output: &syn::ReturnType) -> bool {
+    let path = match output {
+        syn::ReturnType::Type(_, ty) => match &**ty {
+            syn::Type::Path(path) if path.qself.is_none() => &path.path,
+            _ => return false,
+        },
+        syn::ReturnType::Default => return false,
+    };
+    let segment = match path.segments.last() {
+        Some(segment) if segment.ident == "Option" || segment.ident == "Result" => segment,
+        _ => return false,
+    };
+    match &segment.arguments {
+        syn::PathArguments::AngleBracketed(arguments) => matches!(
+            arguments.args.first(),
+            Some(syn::GenericArgument::Type(syn::Type::Path(ty))) if ty.path.is_ident("Self")
+        ),
+        _ => false,
+    }
+}
+
+/// Rebuilds each arm of a `match` delegator with the result of the delegated method, e.g.
+/// `Self::A(a, b) => a` becomes `Self::A(a, b) => Self::A(a.method(), b)`.
+/// With `map` set, the result is an `Option` or a `Result` and the variant is rebuilt
+/// inside of it (`a.method().map(|a| Self::A(a, b))`).
+struct RewrapMatchVisitor<F> {
+    transform: F,
+    map: bool,
+}
+
+impl<F: Fn(&Expr, &ArmAttributes) -> proc_macro2::TokenStream> VisitMut for RewrapMatchVisitor<F> {
     fn visit_arm_mut(&mut self, arm: &mut syn::Arm) {
-        let transformed = self.0(&arm.body);
-        arm.body = parse_quote!(#transformed);
+        let (arm_attrs, other_attrs) = parse_arm_attributes(&arm.attrs);
+        arm.attrs = other_attrs;
+        if arm_attrs.skip {
+            return;
+        }
+
+        let binding = match &*arm.body {
+            Expr::Path(path) if path.qself.is_none() && path.path.get_ident().is_some() => {
+                path.path.get_ident().unwrap().clone()
+            }
+            body => panic!(
+                "Match arms have to return one of the bindings of their pattern to rebuild them (`{}`)",
+                body.to_token_stream()
+            ),
+        };
+        let transformed = (self.transform)(&arm.body, &arm_attrs);
+        let value = if self.map {
+            binding.to_token_stream()
+        } else {
+            transformed.clone()
+        };
+        let mut found = false;
+        let rebuilt = rebuild_pattern(&arm.pat, &binding, &value, &mut found);
+        if !found {
+            panic!(
+                "Binding `{}` was not found in the pattern of the match arm",
+                binding
+            );
+        }
+        arm.body = if self.map {
+            parse_quote!(#transformed.map(|#binding| #rebuilt))
+        } else {
+            parse_quote!(#rebuilt)
+        };
+    }
+}
+
+/// Applies the delegation to each tail expression of a delegator. `match` arms, `if`/`else`
+/// branches and the final expressions of blocks (e.g. after a `let ... else`) are walked
+/// recursively, so that each branch can delegate to a value of a different type.
+/// Attributes of a `match` arm override the delegation for that arm (`#[skip]` keeps the body as is),
+/// arms of nested matches without attributes use the attributes of the enclosing arm.
+fn delegate_tails<F: Fn(&Expr, &ArmAttributes) -> proc_macro2::TokenStream>(
+    expr: &mut Expr,
+    transform: &F,
+    arm_attrs: &ArmAttributes,
+) {
+    match expr {
+        Expr::Match(expr_match) => {
+            for arm in &mut expr_match.arms {
+                let (attrs, other_attrs) = parse_arm_attributes(&arm.attrs);
+                arm.attrs = other_attrs;
+                if attrs.skip {
+                    continue;
+                }
+                let attrs = if attrs.is_empty() { arm_attrs } else { &attrs };
+                if let Expr::Reference(_) = &*arm.body {
+                    // `&a.x` arms keep becoming `&a.x.method()`, like before branches were supported
+                    let transformed = transform(&arm.body, attrs);
+                    arm.body = parse_quote!(#transformed);
+                } else {
+                    delegate_tails(&mut arm.body, transform, attrs);
+                }
+            }
+        }
+        Expr::If(expr_if) => {
+            delegate_block_tail(&mut expr_if.then_branch, transform, arm_attrs);
+            if let Some((_, else_branch)) = &mut expr_if.else_branch {
+                delegate_tails(else_branch, transform, arm_attrs);
+            }
+        }
+        Expr::Block(expr_block) if expr_block.label.is_none() => {
+            delegate_block_tail(&mut expr_block.block, transform, arm_attrs);
+        }
+        // `&self.fallback` in an `if` branch or a block would otherwise become
+        // `&self.fallback.method()`
+        Expr::Reference(_) => {
+            let transformed = transform(&parse_quote!((#expr)), arm_attrs);
+            *expr = parse_quote!(#transformed);
+        }
+        _ => {
+            let transformed = transform(expr, arm_attrs);
+            *expr = parse_quote!(#transformed);
+        }
+    }
+}
+
+/// Applies the delegation to the final expression of a block.
+fn delegate_block_tail<F: Fn(&Expr, &ArmAttributes) -> proc_macro2::TokenStream>(
+    block: &mut syn::Block,
+    transform: &F,
+    arm_attrs: &ArmAttributes,
+) {
+    match block.stmts.last_mut() {
+        Some(syn::Stmt::Expr(tail, None)) => delegate_tails(tail, transform, arm_attrs),
+        _ => panic!(
+            "Blocks in a delegator have to end with an expression (`{}`)",
+            block.to_token_stream()
+        ),
     }
 }
 
@@ -882,9 +2517,10 @@ impl<F: Fn(&Expr) -> proc_macro2::TokenStream> VisitMut for MatchVisitor<F> {
 pub fn delegate(tokens: TokenStream) -> TokenStream {
     let block: DelegationBlock = syn::parse_macro_input!(tokens);
     let sections = block.segments.iter().map(|delegator| {
-        let delegated_expr = &delegator.delegator;
         let functions = delegator.methods.iter().map(|method| {
             let input = &method.method;
+            let receiver = ReceiverKind::of(&input.sig);
+            let delegated_expr = delegator.delegator.for_receiver(receiver, &input.sig.ident);
             let mut signature = input.sig.clone();
             if let Expr::Closure(closure) = delegated_expr {
                 let additional_inputs: Vec<FnArg> = closure
@@ -931,13 +2567,7 @@ pub fn delegate(tokens: TokenStream) -> TokenStream {
             }
 
             // Generate an argument vector from Punctuated list.
-            let args: Vec<Expr> = method.arguments.clone().into_iter().collect();
-
-            // Get name (or index) of the target method or field
-            let name = match &attributes.target_specifier {
-                Some(target) => target.get_member(&input.sig.ident),
-                None => input.sig.ident.clone().into(),
-            };
+            let mut args: Vec<Expr> = method.arguments.clone().into_iter().collect();
 
             let inline = if has_inline_attribute(&attributes.attributes) {
                 quote!()
@@ -946,7 +2576,9 @@ pub fn delegate(tokens: TokenStream) -> TokenStream {
             };
             let visibility = &method.visibility;
 
-            let is_method = method.method.sig.receiver().is_some();
+            // Inside a scope, the delegated expression is the parameter of the closure, even for
+            // associated functions
+            let is_method = receiver != ReceiverKind::None || delegator.scope.is_some();
             let associated_const = &attributes.associated_constant;
             let expr_attr = &attributes.expr_attr;
 
@@ -957,48 +2589,152 @@ pub fn delegate(tokens: TokenStream) -> TokenStream {
                 delegated_expr
             };
 
+            if attributes.newtype {
+                unwrap_self_arguments(&method.method.sig, &mut args, delegated_body);
+            }
+
             let span = input.span();
+            // With an async lock, the method is `async` because of the lock, not the delegated method
+            let async_lock = matches!(&attributes.lock, Some(lock) if lock.is_async);
             let generate_await = attributes
                 .generate_await
-                .unwrap_or_else(|| method.method.sig.asyncness.is_some());
+                .unwrap_or_else(|| method.method.sig.asyncness.is_some() && !async_lock);
 
-            // fn method<'a, A, B> -> method::<A, B>
-            let generic_params = &method.method.sig.generics.params;
-            let generics = if generic_params.is_empty() {
-                quote::quote! {}
-            } else {
-                let span = generic_params.span();
-                let mut params: syn::punctuated::Punctuated<
-                    proc_macro2::TokenStream,
-                    syn::Token![,],
-                > = syn::punctuated::Punctuated::new();
-                for param in generic_params.iter() {
-                    let token = match param {
-                        GenericParam::Lifetime(_) => {
-                            // Do not pass lifetimes to generic arguments explicitly to avoid
-                            // things like https://doc.rust-lang.org/error_codes/E0794.html
-                            // See https://github.com/Kobzol/rust-delegate/issues/85.
-                            continue;
-                        }
-                        GenericParam::Type(t) => {
-                            let token = &t.ident;
-                            let span = t.span();
-                            quote::quote_spanned! {span=> #token }
-                        }
-                        GenericParam::Const(c) => {
-                            let token = &c.ident;
-                            let span = c.span();
-                            quote::quote_spanned! {span=> #token }
+            // Resolves the name (or index) of the target method or field, the called function and
+            // the generic arguments of the call for the given target specifier
+            let resolve_target = |target_specifier: Option<&TargetSpecifier>| {
+                // Get name (or index) of the target method or field
+                let name = match target_specifier {
+                    Some(target) => target.get_member(&input.sig.ident),
+                    None => input.sig.ident.clone().into(),
+                };
+
+                let call = match target_specifier {
+                    Some(TargetSpecifier::Method(call)) => Some(call),
+                    _ => None,
+                };
+                // Path of a called function, reference taken of the delegated expression and
+                // whether the delegated expression should be passed as the first argument
+                let function_call = match target_specifier {
+                    Some(TargetSpecifier::Method(call)) => call
+                        .function_path()
+                        .map(|path| (path.clone(), quote::quote! {}, is_method)),
+                    Some(TargetSpecifier::Function(function)) => {
+                        Some((function.path.clone(), function.reference_tokens(), true))
+                    }
+                    _ => None,
+                };
+                let function_path = function_call.as_ref().map(|(path, _, _)| path);
+                if function_path.is_some() && attributes.target_trait.is_some() {
+                    panic!(
+                        "Cannot use both a function call and `through` attributes for {}",
+                        input.sig.ident
+                    );
+                }
+
+                // fn method<'a, A, B> -> method::<A, B>
+                let generic_params = &method.method.sig.generics.params;
+                let explicit_generics = call.and_then(|call| call.turbofish()).or_else(|| {
+                    match function_path?.path.segments.last()?.arguments {
+                        syn::PathArguments::AngleBracketed(ref arguments) => Some(arguments),
+                        _ => None,
+                    }
+                });
+                let generics = if let Some(explicit_generics) = explicit_generics {
+                    if function_path.is_some() {
+                        // The generic arguments are already a part of the path
+                        quote::quote! {}
+                    } else {
+                        explicit_generics.to_token_stream()
+                    }
+                } else if let Some(GenericsForwarding::Infer) = &attributes.generics {
+                    quote::quote! {}
+                } else if let Some(GenericsForwarding::Explicit(arguments)) = &attributes.generics {
+                    quote::quote! { ::<#arguments> }
+                } else if attributes.generics.is_none()
+                    && has_impl_trait_parameter(&method.method.sig)
+                {
+                    // Generic arguments cannot be specified explicitly when `impl Trait` is used
+                    // in argument position (E0632), so let the compiler infer them.
+                    quote::quote! {}
+                } else {
+                    // `#[generics(T = Vec<T>)]` replaces some of the forwarded parameters
+                    let mapping = match &attributes.generics {
+                        Some(GenericsForwarding::Mapping(mapping)) => mapping.as_slice(),
+                        _ => &[],
+                    };
+                    for (name, _) in mapping {
+                        let is_param = generic_params.iter().any(|param| match param {
+                            GenericParam::Type(t) => t.ident == *name,
+                            GenericParam::Const(c) => c.ident == *name,
+                            GenericParam::Lifetime(_) => false,
+                        });
+                        if !is_param {
+                            panic!(
+                                "`{}` in the `generics` attribute is not a generic parameter of {}",
+                                name, input.sig.ident
+                            );
                         }
+                    }
+                    let mapped = |ident: &Ident| {
+                        mapping
+                            .iter()
+                            .find(|(name, _)| name == ident)
+                            .map(|(_, argument)| argument.to_token_stream())
                     };
-                    params.push(token);
-                }
-                quote::quote_spanned! {span=> ::<#params> }
+
+                    let span = generic_params.span();
+                    let mut params: syn::punctuated::Punctuated<
+                        proc_macro2::TokenStream,
+                        syn::Token![,],
+                    > = syn::punctuated::Punctuated::new();
+                    for param in generic_params.iter() {
+                        let token = match param {
+                            GenericParam::Lifetime(_) => {
+                                // Do not pass lifetimes to generic arguments explicitly to avoid
+                                // things like https://doc.rust-lang.org/error_codes/E0794.html
+                                // See https://github.com/Kobzol/rust-delegate/issues/85.
+                                continue;
+                            }
+                            GenericParam::Type(t) => {
+                                let token = &t.ident;
+                                let span = t.span();
+                                mapped(token).unwrap_or(quote::quote_spanned! {span=> #token })
+                            }
+                            GenericParam::Const(c) => {
+                                let token = &c.ident;
+                                let span = c.span();
+                                mapped(token).unwrap_or(quote::quote_spanned! {span=> #token })
+                            }
+                        };
+                        params.push(token);
+                    }
+                    if params.is_empty() {
+                        quote::quote! {}
+                    } else {
+                        quote::quote_spanned! {span=> ::<#params> }
+                    }
+                };
+                (name, function_call, generics)
             };
 
-            let modify_expr = |expr: &Expr| {
+            let modify_expr = |expr: &Expr, arm: &ArmAttributes| {
+                let target_specifier = arm
+                    .target_specifier
+                    .as_ref()
+                    .or(attributes.target_specifier.as_ref());
+                let (name, function_call, generics) = resolve_target(target_specifier);
+                let expressions = arm.expressions.as_ref().unwrap_or(&attributes.expressions);
+
                 let body = if let Some(target_trait) = &attributes.target_trait {
-                    quote::quote! { #target_trait::#name#generics(#expr, #(#args),*) }
+                    if is_method {
+                        quote::quote! { #target_trait::#name#generics(#expr, #(#args),*) }
+                    } else {
+                        // Associated functions do not receive the delegated expression,
+                        // it is the type that implements the trait instead.
+                        let trait_path = target_trait.path();
+                        quote::quote! { <#expr as #trait_path>::#name#generics(#(#args),*) }
+                    }
                 } else if let Some(AssociatedConstant {
                     const_name,
                     trait_path,
@@ -1011,14 +2747,55 @@ pub fn delegate(tokens: TokenStream) -> TokenStream {
                         }
                         get_const(#expr)
                     }}
+                } else if let Some((function_path, reference, pass_target)) = &function_call {
+                    if *pass_target {
+                        quote::quote! { #function_path#generics(#reference#expr, #(#args),*) }
+                    } else {
+                        quote::quote! { #function_path#generics(#(#args),*) }
+                    }
                 } else if is_method {
-                    match &attributes.target_specifier {
-                        None | Some(TargetSpecifier::Method(_)) => {
+                    match target_specifier {
+                        None | Some(TargetSpecifier::Method(_) | TargetSpecifier::Function(_)) => {
                             quote::quote! { #expr.#name#generics(#(#args),*) }
                         }
                         Some(TargetSpecifier::Field(target)) => {
-                            let reference = target.reference_tokens();
-                            quote::quote! { #reference#expr.#name }
+                            target.access_tokens(&expr.to_token_stream(), &name)
+                        }
+                        Some(TargetSpecifier::FieldCall(target)) => {
+                            if !target.is_optional(&name) {
+                                quote::quote! { (#expr.#name)(#(#args),*) }
+                            } else {
+                                let callable = match receiver {
+                                    ReceiverKind::Shared => quote::quote! { #expr.#name.as_ref() },
+                                    ReceiverKind::Mutable => quote::quote! { #expr.#name.as_mut() },
+                                    _ => quote::quote! { #expr.#name },
+                                };
+                                let function = hygienic_ident("function");
+                                quote::quote! {
+                                    #callable.map(|#function| #function(#(#args),*))
+                                }
+                            }
+                        }
+                        Some(TargetSpecifier::FieldUpdate(update)) => {
+                            if args.len() != update.kind.argument_count() {
+                                panic!(
+                                    "Field update of {} expects {} argument(s), but {} were given",
+                                    input.sig.ident,
+                                    update.kind.argument_count(),
+                                    args.len()
+                                );
+                            }
+                            match update.kind {
+                                FieldUpdateKind::Set => {
+                                    quote::quote! { #expr.#name = #(#args)* }
+                                }
+                                FieldUpdateKind::Replace => quote::quote! {
+                                    ::core::mem::replace(&mut #expr.#name, #(#args)*)
+                                },
+                                FieldUpdateKind::Take => {
+                                    quote::quote! { ::core::mem::take(&mut #expr.#name) }
+                                }
+                            }
                         }
                     }
                 } else {
@@ -1031,7 +2808,7 @@ pub fn delegate(tokens: TokenStream) -> TokenStream {
                     body
                 };
 
-                for expression in &attributes.expressions {
+                for expression in expressions {
                     match expression {
                         ReturnExpression::Into(type_name) => {
                             body = match type_name {
@@ -1051,26 +2828,186 @@ pub fn delegate(tokens: TokenStream) -> TokenStream {
                 }
                 body
             };
-            let mut body = if let Expr::Match(expr_match) = delegated_body {
-                let mut expr_match = expr_match.clone();
-                MatchVisitor(modify_expr).visit_expr_match_mut(&mut expr_match);
-                expr_match.into_token_stream()
-            } else {
-                modify_expr(delegated_body)
+            if attributes.update && receiver != ReceiverKind::Owned {
+                panic!(
+                    "The `update` attribute can only be used on methods taking `self` ({})",
+                    input.sig.ident
+                );
+            }
+            if attributes.rewrap && !matches!(delegated_body, Expr::Match(_)) {
+                panic!(
+                    "The `rewrap` attribute can only be used with a `match` delegator ({})",
+                    input.sig.ident
+                );
+            }
+            if attributes.fallback.is_some() && !delegator.optional {
+                panic!(
+                    "The `fallback` attribute can only be used in an optional (`to?`) segment ({})",
+                    input.sig.ident
+                );
+            }
+            let locked_delegator;
+            let delegated_body = match &attributes.lock {
+                Some(lock) => {
+                    if delegator.optional {
+                        panic!(
+                            "The `lock` attribute cannot be used in an optional (`to?`) segment ({})",
+                            input.sig.ident
+                        );
+                    }
+                    if attributes.update || attributes.rewrap {
+                        panic!(
+                            "The `lock` attribute cannot be combined with `update` or `rewrap` ({})",
+                            input.sig.ident
+                        );
+                    }
+                    if attributes.chain && lock.returns_result() {
+                        panic!(
+                            "The `chain` attribute cannot be combined with a `lock` that returns a `Result` ({})",
+                            input.sig.ident
+                        );
+                    }
+                    locked_delegator =
+                        lock_delegator(delegated_body, lock, receiver, is_method, &input.sig.ident);
+                    &locked_delegator
+                }
+                None => delegated_body,
             };
+            let fallible_lock = matches!(&attributes.lock, Some(lock) if lock.returns_result());
+            // Like `update`, `chain` on a method taking `self` rebuilds each variant of a `match`
+            let rebuild_arms = attributes.update
+                || attributes.rewrap
+                || (attributes.chain && receiver == ReceiverKind::Owned);
+
+            if attributes.guard.is_some()
+                && (attributes.chain || attributes.update || attributes.rewrap)
+            {
+                panic!(
+                    "The `guard` attribute cannot be combined with `chain`, `update` or `rewrap` ({})",
+                    input.sig.ident
+                );
+            }
+            // Calls the delegated method on the target and, with `#[guard]`, maps the guard
+            // to its result (`Ref::map(<target>, |target| target.method())`)
+            let delegate_call = |target: &Expr, arm: &ArmAttributes| match &attributes.guard {
+                Some(guard) => {
+                    let lock = attributes
+                        .lock
+                        .as_ref()
+                        .map(|lock| (lock, receiver.locks_mutably()));
+                    let map = guard.map_function(&signature.output, lock, &input.sig.ident);
+                    let inner = hygienic_ident("target");
+                    let call = modify_expr(&parse_quote!(#inner), arm);
+                    quote! { #map(#target, |#inner| #call) }
+                }
+                None => modify_expr(target, arm),
+            };
+
+            let mut body = match delegated_body {
+                _ if fallible_lock => lock_result_body(
+                    delegated_body,
+                    attributes.lock.as_ref().and_then(|lock| lock.error.as_ref()),
+                    |guard| delegate_call(guard, &ArmAttributes::default()),
+                ),
+                _ if delegator.optional => {
+                    let fallback = match &attributes.fallback {
+                        Some(fallback) => fallback.clone(),
+                        // Methods without a return value do nothing
+                        None if matches!(signature.output, syn::ReturnType::Default) => {
+                            Fallback::Default
+                        }
+                        None => Fallback::None,
+                    };
+                    optional_body(
+                        delegated_body,
+                        receiver,
+                        &fallback,
+                        &input.sig.ident,
+                        |target| delegate_call(target, &ArmAttributes::default()),
+                    )
+                }
+                Expr::Match(expr_match) if rebuild_arms => {
+                    let mut expr_match = expr_match.clone();
+                    RewrapMatchVisitor {
+                        transform: modify_expr,
+                        map: returns_wrapped_self(&signature.output),
+                    }
+                    .visit_expr_match_mut(&mut expr_match);
+                    expr_match.into_token_stream()
+                }
+                _ if attributes.update => {
+                    let body = modify_expr(delegated_body, &ArmAttributes::default());
+                    rebuild_self(delegated_body, &body, "update", &input.sig.ident)
+                }
+                // The branches can delegate to values of different types, so with `chain` the
+                // result is discarded in each branch (`A(a) => { a.method(); }`)
+                Expr::Match(_) | Expr::If(_) | Expr::Block(_) if attributes.chain => {
+                    let mut delegated_body = delegated_body.clone();
+                    delegate_tails(
+                        &mut delegated_body,
+                        &|target: &Expr, arm: &ArmAttributes| {
+                            let call = delegate_call(target, arm);
+                            quote! { { #call; } }
+                        },
+                        &ArmAttributes::default(),
+                    );
+                    delegated_body.into_token_stream()
+                }
+                Expr::Match(_) | Expr::If(_) | Expr::Block(_) => {
+                    let mut delegated_body = delegated_body.clone();
+                    delegate_tails(
+                        &mut delegated_body,
+                        &delegate_call,
+                        &ArmAttributes::default(),
+                    );
+                    delegated_body.into_token_stream()
+                }
+                _ => delegate_call(delegated_body, &ArmAttributes::default()),
+            };
+
+            if let Some(scope) = &delegator.scope {
+                // Like return modifiers, the template is applied inside the closure
+                if let Some(expr_template) = expr_attr {
+                    body = expr_template.expand_template(&body);
+                }
+                body = scope.wrap(&body);
+            }
 
             if let syn::ReturnType::Default = &signature.output {
                 body = quote::quote! { #body; };
             };
 
-            if let Some(expr_template) = expr_attr {
+            if let Some(expr_template) = expr_attr.as_ref().filter(|_| delegator.scope.is_none()) {
                 body = expr_template.expand_template(&body);
             }
 
+            if attributes.chain {
+                if let syn::ReturnType::Default = &signature.output {
+                    panic!(
+                        "The `chain` attribute requires a `Self` or `&mut Self` return type ({})",
+                        input.sig.ident
+                    );
+                }
+                if !(rebuild_arms && matches!(delegated_body, Expr::Match(_))) {
+                    body = chain_body(&body, receiver, delegated_body, &input.sig.ident);
+                }
+            }
+
+            // `Self { inner: ..., ..self }` has no effect for structs with a single field
+            let allow_update = if (attributes.chain || attributes.update)
+                && receiver == ReceiverKind::Owned
+                && !matches!(delegated_body, Expr::Match(_))
+            {
+                quote! { #[allow(clippy::needless_update)] }
+            } else {
+                quote!()
+            };
+
             let attrs = &attributes.attributes;
             quote::quote_spanned! {span=>
                 #(#attrs)*
                 #inline
+                #allow_update
                 #visibility #signature {
                     #body
                 }
@@ -0,0 +1,187 @@
+use delegate::delegate;
+
+struct Disk {
+    size: usize,
+}
+
+impl Disk {
+    fn len(&self) -> usize {
+        self.size
+    }
+}
+
+#[test]
+fn test_if_let() {
+    struct Cache {
+        cache: Option<Vec<u8>>,
+        fallback: Disk,
+    }
+
+    impl Cache {
+        delegate! {
+            // if let Some(cache) = &self.cache { cache.len() } else { (&self.fallback).len() }
+            to if let Some(cache) = &self.cache { cache } else { &self.fallback } {
+                fn len(&self) -> usize;
+            }
+        }
+    }
+
+    let cache = Cache {
+        cache: Some(vec![1, 2]),
+        fallback: Disk { size: 5 },
+    };
+    assert_eq!(cache.len(), 2);
+    let cache = Cache {
+        cache: None,
+        fallback: Disk { size: 5 },
+    };
+    assert_eq!(cache.len(), 5);
+}
+
+#[test]
+fn test_else_if() {
+    struct Storage {
+        memory: Vec<u8>,
+        text: String,
+        disk: Disk,
+        mode: u8,
+    }
+
+    impl Storage {
+        delegate! {
+            to if self.mode == 0 {
+                &self.memory
+            } else if self.mode == 1 {
+                &self.text
+            } else {
+                &self.disk
+            } {
+                fn len(&self) -> usize;
+            }
+        }
+    }
+
+    let storage = |mode| Storage {
+        memory: vec![1],
+        text: "ab".to_string(),
+        disk: Disk { size: 3 },
+        mode,
+    };
+    assert_eq!(storage(0).len(), 1);
+    assert_eq!(storage(1).len(), 2);
+    assert_eq!(storage(2).len(), 3);
+}
+
+#[test]
+fn test_let_else_block() {
+    struct Lazy {
+        value: Option<String>,
+        disk: Disk,
+    }
+
+    impl Lazy {
+        delegate! {
+            // { let Some(value) = &self.value else { return self.disk.len() }; value.len() }
+            to {
+                let Some(value) = &self.value else {
+                    return self.disk.len();
+                };
+                value
+            } {
+                fn len(&self) -> usize;
+            }
+        }
+    }
+
+    let lazy = Lazy {
+        value: Some("abc".to_string()),
+        disk: Disk { size: 7 },
+    };
+    assert_eq!(lazy.len(), 3);
+    let lazy = Lazy {
+        value: None,
+        disk: Disk { size: 7 },
+    };
+    assert_eq!(lazy.len(), 7);
+}
+
+#[test]
+fn test_nested_match() {
+    enum Inner {
+        Memory(Vec<u8>),
+        Disk(Disk),
+    }
+
+    enum Outer {
+        Inner(Inner),
+        Text(String),
+        Empty,
+    }
+
+    impl Outer {
+        delegate! {
+            to match self {
+                Outer::Inner(inner) => match inner {
+                    Inner::Memory(memory) => memory,
+                    Inner::Disk(disk) => disk,
+                },
+                Outer::Text(text) => {
+                    let text = text.trim();
+                    text
+                }
+                #[skip]
+                Outer::Empty => 0,
+            } {
+                // match self {
+                //     Outer::Inner(inner) => match inner {
+                //         Inner::Memory(memory) => memory.len(),
+                //         Inner::Disk(disk) => disk.len(),
+                //     },
+                //     Outer::Text(text) => {
+                //         let text = text.trim();
+                //         text.len()
+                //     }
+                //     Outer::Empty => 0,
+                // }
+                fn len(&self) -> usize;
+            }
+        }
+    }
+
+    assert_eq!(Outer::Inner(Inner::Memory(vec![1, 2])).len(), 2);
+    assert_eq!(Outer::Inner(Inner::Disk(Disk { size: 4 })).len(), 4);
+    assert_eq!(Outer::Text(" ab ".to_string()).len(), 2);
+    assert_eq!(Outer::Empty.len(), 0);
+}
+
+#[test]
+fn test_match_arm_reference() {
+    enum Storage {
+        Local(Vec<u8>),
+        Shared { items: Vec<u8> },
+    }
+
+    impl Storage {
+        delegate! {
+            to match self {
+                Storage::Local(items) => &items,
+                Storage::Shared { items } => &items,
+            } {
+                // `&items` arms are not parenthesized, the method is called on `items`
+                // match self {
+                //     Storage::Local(items) => &items.clear(),
+                //     Storage::Shared { items } => &items.clear(),
+                // }
+                fn clear(&mut self);
+            }
+        }
+    }
+
+    let mut local = Storage::Local(vec![1, 2]);
+    local.clear();
+    assert!(matches!(local, Storage::Local(ref items) if items.is_empty()));
+
+    let mut shared = Storage::Shared { items: vec![1] };
+    shared.clear();
+    assert!(matches!(shared, Storage::Shared { ref items } if items.is_empty()));
+}
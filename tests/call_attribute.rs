@@ -0,0 +1,89 @@
+use delegate::delegate;
+
+trait Describe {
+    fn describe(&self, prefix: &str) -> String;
+}
+
+struct Inner(String);
+
+impl Inner {
+    fn describe(&self, prefix: &str) -> String {
+        format!("{prefix}inherent {}", self.0)
+    }
+
+    fn convert<T: From<u8>>(&self, value: u8) -> T {
+        T::from(value)
+    }
+
+    fn new<T: ToString>(value: T) -> Self {
+        Inner(value.to_string())
+    }
+}
+
+impl Describe for Inner {
+    fn describe(&self, prefix: &str) -> String {
+        format!("{prefix}trait {}", self.0)
+    }
+}
+
+struct Wrapper {
+    inner: Inner,
+}
+
+impl Wrapper {
+    delegate! {
+        to self.inner.0 {
+            // self.inner.0.parse::<u32>()
+            #[call(parse::<u32>)]
+            fn parse(&self) -> Result<u32, std::num::ParseIntError>;
+        }
+        to self.inner {
+            // self.inner.convert::<u64>(value)
+            #[call(convert::<u64>)]
+            fn convert(&self, value: u8) -> u64;
+        }
+        to &self.inner {
+            // Inner::describe(&self.inner, prefix)
+            #[call(Inner::describe)]
+            fn describe_inherent(&self, prefix: &str) -> String;
+
+            // <Inner as Describe>::describe(&self.inner, prefix)
+            #[call(<Inner as Describe>::describe)]
+            fn describe_trait(&self, prefix: &str) -> String;
+
+            // Describe::describe(&self.inner, prefix)
+            #[call(Describe::describe)]
+            fn describe(&self, prefix: &str) -> String;
+        }
+        to Inner {
+            // Inner::new::<u32>(value)
+            #[call(new::<u32>)]
+            fn new_inner(value: u32) -> Inner;
+
+            // Inner::new::<&str>(value)
+            #[call(Inner::new::<&str>)]
+            fn new_inner_from_str(value: &str) -> Inner;
+        }
+    }
+}
+
+#[test]
+fn test_call_turbofish() {
+    let wrapper = Wrapper {
+        inner: Inner("42".to_string()),
+    };
+    assert_eq!(wrapper.parse(), Ok(42));
+    assert_eq!(wrapper.convert(3), 3);
+    assert_eq!(Wrapper::new_inner(5).0, "5");
+    assert_eq!(Wrapper::new_inner_from_str("a").0, "a");
+}
+
+#[test]
+fn test_call_path() {
+    let wrapper = Wrapper {
+        inner: Inner("x".to_string()),
+    };
+    assert_eq!(wrapper.describe_inherent("> "), "> inherent x");
+    assert_eq!(wrapper.describe_trait("> "), "> trait x");
+    assert_eq!(wrapper.describe("> "), "> trait x");
+}
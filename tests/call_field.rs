@@ -0,0 +1,104 @@
+use delegate::delegate;
+
+type CloseCallback = Box<dyn Fn(&str) -> String>;
+
+struct Callbacks {
+    on_event: Box<dyn Fn(u32) -> bool>,
+    on_count: Box<dyn FnMut() -> usize>,
+    formatter: fn(&str, u32) -> String,
+    on_close: Option<CloseCallback>,
+    on_reset: Option<Box<dyn FnMut(u32)>>,
+}
+
+struct Plugin {
+    callbacks: Callbacks,
+}
+
+impl Plugin {
+    delegate! {
+        to self.callbacks {
+            // (self.callbacks.on_event)(event)
+            #[call_field]
+            fn on_event(&self, event: u32) -> bool;
+
+            // (self.callbacks.on_count)()
+            #[call_field(on_count)]
+            fn count(&mut self) -> usize;
+
+            // (self.callbacks.formatter)(prefix, value.into())
+            #[call_field(formatter)]
+            fn format(&self, prefix: &str, #[into] value: u8) -> String;
+
+            // self.callbacks.on_close.as_ref().map(|function| function(function))
+            #[call_field(optional)]
+            fn on_close(&self, function: &str) -> Option<String>;
+
+            // self.callbacks.on_reset.as_mut().map(|function| function(value))
+            #[call_field(optional on_reset)]
+            fn reset(&mut self, value: u32) -> Option<()>;
+        }
+        to self {
+            // (self.callbacks.on_event)(event)
+            #[call_field(callbacks.on_event)]
+            fn on_event_nested(&self, event: u32) -> bool;
+        }
+    }
+}
+
+#[test]
+fn test_call_field() {
+    let mut counter = 0;
+    let mut plugin = Plugin {
+        callbacks: Callbacks {
+            on_event: Box::new(|event| event > 1),
+            on_count: Box::new(move || {
+                counter += 1;
+                counter
+            }),
+            formatter: |prefix, value| format!("{prefix}{value}"),
+            on_close: Some(Box::new(|reason| format!("closed: {reason}"))),
+            on_reset: None,
+        },
+    };
+    assert!(!plugin.on_event(1));
+    assert!(plugin.on_event_nested(2));
+    assert_eq!(plugin.count(), 1);
+    assert_eq!(plugin.count(), 2);
+    assert_eq!(plugin.format("> ", 3), "> 3");
+    assert_eq!(plugin.on_close("done"), Some("closed: done".to_string()));
+    assert_eq!(plugin.reset(1), None);
+
+    plugin.callbacks.on_close = None;
+    plugin.callbacks.on_reset = Some(Box::new(|_| {}));
+    assert_eq!(plugin.on_close("done"), None);
+    assert_eq!(plugin.reset(1), Some(()));
+}
+
+#[test]
+fn test_call_field_named_optional() {
+    struct Callbacks {
+        optional: fn(u32) -> u32,
+    }
+
+    struct Wrapper {
+        callbacks: Callbacks,
+    }
+
+    impl Wrapper {
+        delegate! {
+            to self.callbacks {
+                // A bare `optional` on a method with the same name calls the field:
+                // (self.callbacks.optional)(value)
+                #[call_field(optional)]
+                fn optional(&self, value: u32) -> u32;
+            }
+        }
+    }
+
+    let wrapper = Wrapper {
+        callbacks: Callbacks {
+            optional: |value| value + 1,
+        },
+    };
+    assert_eq!(wrapper.optional(1), 2);
+}
@@ -0,0 +1,101 @@
+use delegate::delegate;
+
+mod helpers {
+    pub fn describe(value: &[u32], prefix: &str) -> String {
+        format!("{prefix}{value:?}")
+    }
+
+    pub fn sum(value: &[u32]) -> u32 {
+        value.iter().sum()
+    }
+
+    pub fn push_twice(value: &mut Vec<u32>, item: u32) {
+        value.push(item);
+        value.push(item);
+    }
+
+    pub fn into_len(value: Vec<u32>) -> usize {
+        value.len()
+    }
+
+    pub fn parse_as<T: std::str::FromStr>(value: &str) -> Option<T> {
+        value.parse().ok()
+    }
+}
+
+struct Wrapper {
+    buf: Vec<u32>,
+    text: String,
+}
+
+impl Wrapper {
+    delegate! {
+        to self.buf {
+            // helpers::describe(&self.buf, prefix)
+            #[call_fn(&helpers::describe)]
+            fn describe(&self, prefix: &str) -> String;
+
+            // helpers::sum(&self.buf).into()
+            #[call_fn(&helpers::sum)]
+            #[into]
+            fn sum(&self) -> u64;
+
+            // helpers::push_twice(&mut self.buf, item.into())
+            #[call_fn(&mut helpers::push_twice)]
+            fn push_twice(&mut self, #[into] item: u8);
+
+            // std::mem::take(&mut self.buf)
+            #[call_fn(&mut std::mem::take)]
+            fn take_buf(&mut self) -> Vec<u32>;
+
+            // helpers::into_len(self.buf)
+            #[call_fn(helpers::into_len)]
+            fn into_len(self) -> usize;
+        }
+        to self.text.as_str() {
+            // helpers::parse_as::<u8>(self.text.as_str())
+            #[call_fn(helpers::parse_as::<u8>)]
+            fn parse_u8(&self) -> Option<u8>;
+        }
+    }
+}
+
+#[test]
+fn test_call_fn() {
+    let mut wrapper = Wrapper {
+        buf: vec![1, 2],
+        text: "7".to_string(),
+    };
+    assert_eq!(wrapper.describe("> "), "> [1, 2]");
+    assert_eq!(wrapper.sum(), 3);
+    wrapper.push_twice(3);
+    assert_eq!(wrapper.buf, vec![1, 2, 3, 3]);
+    assert_eq!(wrapper.parse_u8(), Some(7));
+    assert_eq!(wrapper.take_buf(), vec![1, 2, 3, 3]);
+    assert!(wrapper.buf.is_empty());
+    wrapper.buf.push(1);
+    assert_eq!(wrapper.into_len(), 1);
+}
+
+#[test]
+fn test_call_fn_match() {
+    enum Values {
+        Small(Vec<u32>),
+        Large { values: Vec<u32> },
+    }
+
+    impl Values {
+        delegate! {
+            to match self {
+                Values::Small(values) => values,
+                Values::Large { values } => values,
+            } {
+                #[call_fn(helpers::sum)]
+                fn sum(&self) -> u32;
+            }
+        }
+    }
+
+    assert_eq!(Values::Small(vec![1]).sum(), 1);
+    assert_eq!(Values::Large { values: vec![1, 2] }.sum(), 3);
+}
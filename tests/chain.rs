@@ -0,0 +1,236 @@
+use delegate::delegate;
+
+#[test]
+fn test_chain_mutable() {
+    #[derive(Default)]
+    struct InnerBuilder {
+        name: String,
+        size: u32,
+    }
+
+    impl InnerBuilder {
+        fn name(&mut self, name: &str) -> &mut Self {
+            self.name = name.to_string();
+            self
+        }
+        fn size(&mut self, size: u32) -> &mut Self {
+            self.size = size;
+            self
+        }
+        fn reset(&mut self) {
+            *self = Self::default();
+        }
+        fn describe(&self) -> String {
+            format!("{}: {}", self.name, self.size)
+        }
+    }
+
+    #[derive(Default)]
+    struct Builder {
+        inner: InnerBuilder,
+        built: u32,
+    }
+
+    impl Builder {
+        delegate! {
+            #[chain]
+            to self.inner {
+                // self.inner.name(name); self
+                pub fn name(&mut self, name: &str) -> &mut Self;
+
+                // self.inner.size(size); self
+                pub fn size(&mut self, size: u32) -> &mut Self;
+
+                // self.inner.reset(); self
+                pub fn reset(&mut self) -> &mut Self;
+
+                // self.inner.describe()
+                #[no_inherit(chain)]
+                pub fn describe(&self) -> String;
+            }
+        }
+
+        fn build(&mut self) -> String {
+            self.built += 1;
+            self.describe()
+        }
+    }
+
+    let mut builder = Builder::default();
+    assert_eq!(builder.name("foo").size(3).build(), "foo: 3");
+    assert_eq!(builder.reset().size(1).build(), ": 1");
+    assert_eq!(builder.built, 2);
+}
+
+#[test]
+fn test_chain_shared() {
+    use std::cell::Cell;
+
+    struct Counter(Cell<u32>);
+
+    impl Counter {
+        fn increment(&self) -> u32 {
+            self.0.set(self.0.get() + 1);
+            self.0.get()
+        }
+    }
+
+    struct Wrapper(Counter);
+
+    impl Wrapper {
+        delegate! {
+            to self.0 {
+                // self.0.increment(); self
+                #[chain]
+                fn increment(&self) -> &Self;
+            }
+        }
+    }
+
+    let wrapper = Wrapper(Counter(Cell::new(0)));
+    wrapper.increment().increment();
+    assert_eq!(wrapper.0 .0.get(), 2);
+}
+
+#[test]
+fn test_chain_owned() {
+    #[derive(Default)]
+    struct InnerBuilder {
+        name: String,
+        size: u32,
+    }
+
+    impl InnerBuilder {
+        fn name(self, name: &str) -> Self {
+            Self {
+                name: name.to_string(),
+                ..self
+            }
+        }
+        fn size(self, size: u32) -> Self {
+            Self { size, ..self }
+        }
+    }
+
+    #[derive(Default)]
+    struct Builder {
+        inner: InnerBuilder,
+    }
+
+    impl Builder {
+        delegate! {
+            #[chain]
+            to self.inner {
+                // Self { inner: self.inner.name(name), ..self }
+                pub fn name(self, name: &str) -> Self;
+
+                // Self { inner: self.inner.size(size), ..self }
+                pub fn size(self, size: u32) -> Self;
+            }
+        }
+    }
+
+    struct TupleBuilder(InnerBuilder);
+
+    impl TupleBuilder {
+        delegate! {
+            to self.0 {
+                // Self { 0: self.0.size(size), ..self }
+                #[chain]
+                pub fn size(self, size: u32) -> Self;
+            }
+        }
+    }
+
+    let builder = Builder::default().name("foo").size(2);
+    assert_eq!(builder.inner.name, "foo");
+    assert_eq!(builder.inner.size, 2);
+
+    let builder = TupleBuilder(InnerBuilder::default()).size(3);
+    assert_eq!(builder.0.size, 3);
+}
+
+#[test]
+fn test_chain_owned_match() {
+    #[derive(Debug, PartialEq)]
+    struct Inner(u32);
+
+    impl Inner {
+        fn add(self, value: u32) -> Self {
+            Self(self.0 + value)
+        }
+    }
+
+    #[derive(Debug, PartialEq)]
+    enum Builder {
+        A(Inner),
+        B { name: String, inner: Inner },
+    }
+
+    impl Builder {
+        delegate! {
+            #[chain]
+            to match self {
+                Builder::A(inner) => inner,
+                Builder::B { name, inner } => inner,
+            } {
+                // Builder::A(inner.add(value)) or Builder::B { name, inner: inner.add(value) }
+                pub fn add(self, value: u32) -> Self;
+            }
+        }
+    }
+
+    assert_eq!(Builder::A(Inner(1)).add(2).add(3), Builder::A(Inner(6)));
+    assert_eq!(
+        Builder::B {
+            name: "b".to_string(),
+            inner: Inner(1)
+        }
+        .add(2),
+        Builder::B {
+            name: "b".to_string(),
+            inner: Inner(3)
+        }
+    );
+}
+
+#[test]
+fn test_chain_borrowed_match() {
+    #[derive(Default)]
+    struct Counter {
+        count: usize,
+    }
+
+    impl Counter {
+        fn push(&mut self, _value: u32) -> usize {
+            self.count += 1;
+            self.count
+        }
+    }
+
+    enum Collection {
+        Vec(Vec<u32>),
+        Counter(Counter),
+    }
+
+    impl Collection {
+        delegate! {
+            #[chain]
+            to match self {
+                Collection::Vec(v) => v,
+                Collection::Counter(c) => c,
+            } {
+                // match self { Vec(v) => { v.push(value); } Counter(c) => { c.push(value); } }; self
+                pub fn push(&mut self, value: u32) -> &mut Self;
+            }
+        }
+    }
+
+    let mut vec = Collection::Vec(Vec::new());
+    vec.push(1).push(2);
+    assert!(matches!(vec, Collection::Vec(ref v) if v == &[1, 2]));
+
+    let mut counter = Collection::Counter(Counter::default());
+    counter.push(1).push(2).push(3);
+    assert!(matches!(counter, Collection::Counter(Counter { count: 3 })));
+}
@@ -111,3 +111,57 @@ fn test_delegate_enum_into() {
     };
     assert_eq!(c.into_num(), IntoUsize(3));
 }
+
+#[test]
+fn test_delegate_enum_arm_overrides() {
+    struct Counter {
+        count: u16,
+    }
+    impl Counter {
+        fn len(&self) -> u16 {
+            self.count
+        }
+        fn checked_len(&self) -> Option<usize> {
+            Some(self.count.into())
+        }
+    }
+
+    enum Collection {
+        Items(Vec<u8>),
+        Text(String),
+        Counter(Counter),
+        Checked(Counter),
+        Empty,
+    }
+
+    impl Collection {
+        delegate! {
+            to match self {
+                Collection::Items(items) => items,
+                Collection::Text(text) => text,
+                #[into]
+                Collection::Counter(counter) => counter,
+                #[call(checked_len)]
+                #[unwrap]
+                Collection::Checked(counter) => counter,
+                #[skip]
+                Collection::Empty => 0,
+            } {
+                // match self {
+                //     Collection::Items(items) => items.len(),
+                //     Collection::Text(text) => text.len(),
+                //     Collection::Counter(counter) => ::core::convert::Into::into(counter.len()),
+                //     Collection::Checked(counter) => counter.checked_len().unwrap(),
+                //     Collection::Empty => 0,
+                // }
+                fn len(&self) -> usize;
+            }
+        }
+    }
+
+    assert_eq!(Collection::Items(vec![1, 2]).len(), 2);
+    assert_eq!(Collection::Text("abc".to_string()).len(), 3);
+    assert_eq!(Collection::Counter(Counter { count: 4 }).len(), 4);
+    assert_eq!(Collection::Checked(Counter { count: 5 }).len(), 5);
+    assert_eq!(Collection::Empty.len(), 0);
+}
@@ -0,0 +1,104 @@
+use delegate::delegate;
+
+#[derive(Default)]
+struct Datum {
+    value: u32,
+    label: String,
+    tags: Vec<String>,
+}
+
+struct DatumWrapper(Datum);
+
+impl DatumWrapper {
+    delegate! {
+        to self.0 {
+            /// Expands to `self.0.value = value;`
+            #[field_set]
+            fn set_value(&mut self, value: u32);
+
+            /// Expands to `self.0.value = value;`
+            #[field_set(value)]
+            fn store(&mut self, value: u32);
+
+            /// Expands to `self.0.label = label.into();`
+            #[field_set]
+            fn set_label(&mut self, #[into] label: &str);
+
+            /// Expands to `::core::mem::replace(&mut self.0.label, label)`
+            #[field_replace]
+            fn replace_label(&mut self, label: String) -> String;
+
+            /// Expands to `::core::mem::take(&mut self.0.tags)`
+            #[field_take]
+            fn take_tags(&mut self) -> Vec<String>;
+
+            /// Expands to `self.0.tags = vec![self.0.label.clone()];`
+            #[field_set(tags)]
+            fn reset_tags(&mut self, [ vec![self.0.label.clone()] ]);
+        }
+    }
+}
+
+#[test]
+fn test_field_update() {
+    let mut wrapper = DatumWrapper(Datum::default());
+    wrapper.set_value(1);
+    assert_eq!(wrapper.0.value, 1);
+    wrapper.store(2);
+    assert_eq!(wrapper.0.value, 2);
+
+    wrapper.set_label("foo");
+    assert_eq!(wrapper.0.label, "foo");
+    assert_eq!(wrapper.replace_label("bar".to_string()), "foo");
+    assert_eq!(wrapper.0.label, "bar");
+
+    wrapper.reset_tags();
+    assert_eq!(wrapper.take_tags(), vec!["bar".to_string()]);
+    assert!(wrapper.0.tags.is_empty());
+}
+
+#[test]
+fn test_field_update_match() {
+    struct A {
+        value: u32,
+    }
+    struct B {
+        value: u32,
+    }
+
+    enum Enum {
+        A(A),
+        B { inner: B },
+    }
+
+    impl Enum {
+        delegate! {
+            to match self {
+                Enum::A(a) => a,
+                Enum::B { inner } => inner,
+            } {
+                #[field]
+                fn value(&self) -> u32;
+
+                #[field_set]
+                fn set_value(&mut self, #[into] value: u8);
+
+                #[field_replace(value)]
+                fn swap(&mut self, value: u32) -> u32;
+            }
+        }
+    }
+
+    let mut a = Enum::A(A { value: 1 });
+    a.set_value(5);
+    assert_eq!(a.value(), 5);
+    assert_eq!(a.swap(6), 5);
+    assert_eq!(a.value(), 6);
+
+    let mut b = Enum::B {
+        inner: B { value: 1 },
+    };
+    b.set_value(3);
+    assert_eq!(b.swap(4), 3);
+    assert_eq!(b.value(), 4);
+}
@@ -66,3 +66,280 @@ fn test_fields() {
     assert_eq!(wrapper.x(), wrapper.0.xy.0);
     assert_eq!(wrapper.y(), &wrapper.0.xy.1);
 }
+
+#[test]
+fn test_segment_fields() {
+    struct Config {
+        name: String,
+        retries: u32,
+        limits: (u32, u32),
+    }
+
+    impl Config {
+        fn retries(&self) -> u32 {
+            self.retries * 2
+        }
+    }
+
+    struct Wrapper {
+        config: Config,
+    }
+
+    impl Wrapper {
+        delegate! {
+            #[field(&)]
+            to self.config {
+                /// Expands to `&self.config.name`
+                fn name(&self) -> &String;
+
+                /// Expands to `&self.config.limits`
+                fn limits(&self) -> &(u32, u32);
+
+                /// Expands to `self.config.retries()`
+                #[call]
+                fn retries(&self) -> u32;
+
+                /// Expands to `self.config.retries`
+                #[field(retries)]
+                fn raw_retries(&self) -> u32;
+            }
+            #[field(&mut)]
+            to self.config {
+                /// Expands to `&mut self.config.name`
+                #[field(&mut name)]
+                fn name_mut(&mut self) -> &mut String;
+            }
+            #[field]
+            to self.config.limits {
+                /// Expands to `self.config.limits.0`
+                #[field(0)]
+                fn min(&self) -> u32;
+            }
+        }
+    }
+
+    let mut wrapper = Wrapper {
+        config: Config {
+            name: "foo".to_string(),
+            retries: 3,
+            limits: (1, 2),
+        },
+    };
+    assert_eq!(wrapper.name(), "foo");
+    assert_eq!(wrapper.limits(), &(1, 2));
+    assert_eq!(wrapper.retries(), 6);
+    assert_eq!(wrapper.raw_retries(), 3);
+    assert_eq!(wrapper.min(), 1);
+    wrapper.name_mut().push('!');
+    assert_eq!(wrapper.name(), "foo!");
+}
+
+#[test]
+fn test_field_modes() {
+    use std::sync::Arc;
+
+    #[derive(Clone)]
+    struct Inner {
+        name: String,
+        nickname: Option<String>,
+        parent: Option<u32>,
+        shared: Arc<u32>,
+        count: u32,
+    }
+
+    impl Inner {
+        fn total(&self) -> u32 {
+            self.count * 10
+        }
+    }
+
+    struct Wrapper {
+        inner: Inner,
+    }
+
+    impl Wrapper {
+        delegate! {
+            to self.inner {
+                /// Expands to `::core::clone::Clone::clone(&self.inner.shared)`
+                #[field(clone)]
+                fn shared(&self) -> Arc<u32>;
+
+                /// Expands to `*&self.inner.count`
+                #[field(copy count)]
+                fn counter(&self) -> u32;
+
+                /// Expands to `self.inner.name.as_ref()`
+                #[field(as_ref)]
+                fn name(&self) -> &str;
+
+                /// Expands to `self.inner.parent.as_ref()`
+                #[field(as_ref)]
+                fn parent(&self) -> Option<&u32>;
+
+                /// Expands to `self.inner.nickname.as_deref()`
+                #[field(as_deref)]
+                fn nickname(&self) -> Option<&str>;
+
+                /// Expands to `self.inner.parent.as_mut()`
+                #[field(as_mut parent)]
+                fn parent_mut(&mut self) -> Option<&mut u32>;
+            }
+            #[field(clone)]
+            to self.inner {
+                /// Expands to `::core::clone::Clone::clone(&self.inner.nickname)`
+                #[field(clone nickname)]
+                fn nickname_owned(&self) -> Option<String>;
+
+                /// Expands to `self.inner.total()`
+                #[call]
+                fn total(&self) -> u32;
+            }
+            #[field(clone)]
+            to self {
+                /// Expands to `::core::clone::Clone::clone(&self.inner)`
+                fn inner(&self) -> Inner;
+            }
+        }
+    }
+
+    let mut wrapper = Wrapper {
+        inner: Inner {
+            name: "foo".to_string(),
+            nickname: Some("bar".to_string()),
+            parent: Some(1),
+            shared: Arc::new(5),
+            count: 2,
+        },
+    };
+    assert_eq!(*wrapper.shared(), 5);
+    assert_eq!(Arc::strong_count(&wrapper.inner.shared), 1);
+    assert_eq!(wrapper.counter(), 2);
+    assert_eq!(wrapper.name(), "foo");
+    assert_eq!(wrapper.parent(), Some(&1));
+    assert_eq!(wrapper.nickname(), Some("bar"));
+    assert_eq!(wrapper.nickname_owned(), Some("bar".to_string()));
+    assert_eq!(wrapper.total(), 20);
+    assert_eq!(wrapper.inner().count, 2);
+    *wrapper.parent_mut().unwrap() = 3;
+    assert_eq!(wrapper.parent(), Some(&3));
+}
+
+#[test]
+fn test_nested_field_paths() {
+    struct Limits {
+        max: u32,
+        range: (u32, (u32, u32)),
+    }
+
+    struct Config {
+        limits: Limits,
+        name: Option<String>,
+    }
+
+    struct Wrapper {
+        config: Config,
+    }
+
+    impl Wrapper {
+        delegate! {
+            to self.config {
+                /// Expands to `&self.config.limits.max`
+                #[field(&limits.max)]
+                fn max(&self) -> &u32;
+
+                /// Expands to `self.config.limits.range.0`
+                #[field(limits.range.0)]
+                fn start(&self) -> u32;
+
+                /// Expands to `&mut self.config.limits.range.1.1`
+                #[field(&mut limits.range.1.1)]
+                fn inner_end_mut(&mut self) -> &mut u32;
+
+                /// Expands to `self.config.name.as_deref()`
+                #[field(as_deref name)]
+                fn name(&self) -> Option<&str>;
+
+                /// Expands to `self.config.limits.max = max;`
+                #[field_set(limits.max)]
+                fn set_max(&mut self, max: u32);
+            }
+            to self {
+                /// Expands to `self.config.limits.range.1.0`
+                #[field(config.limits.range.1.0)]
+                fn inner_start(&self) -> u32;
+            }
+        }
+    }
+
+    let mut wrapper = Wrapper {
+        config: Config {
+            limits: Limits {
+                max: 10,
+                range: (1, (2, 3)),
+            },
+            name: None,
+        },
+    };
+    assert_eq!(wrapper.max(), &10);
+    assert_eq!(wrapper.start(), 1);
+    assert_eq!(wrapper.inner_start(), 2);
+    *wrapper.inner_end_mut() = 4;
+    assert_eq!(wrapper.config.limits.range.1 .1, 4);
+    assert_eq!(wrapper.name(), None);
+    wrapper.set_max(20);
+    assert_eq!(wrapper.max(), &20);
+}
+
+#[test]
+fn test_field_named_like_mode() {
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    struct Limits {
+        max: u32,
+        copy: u32,
+    }
+
+    struct Inner {
+        clone: Limits,
+    }
+
+    struct Wrapper {
+        inner: Inner,
+    }
+
+    impl Wrapper {
+        delegate! {
+            to self.inner {
+                /// A bare mode on a method with the same name returns the field: `self.inner.clone`
+                #[field(clone)]
+                fn clone(&self) -> Limits;
+
+                /// Expands to `&self.inner.clone`
+                #[field(&clone)]
+                fn clone_ref(&self) -> &Limits;
+
+                /// A mode followed by `.` starts a field path: `self.inner.clone.max`
+                #[field(clone.max)]
+                fn max(&self) -> u32;
+
+                /// Expands to `*&self.inner.clone.max`
+                #[field(copy clone.max)]
+                fn max_copy(&self) -> u32;
+            }
+            to self.inner.clone {
+                /// Expands to `self.inner.clone.copy`
+                #[field(copy)]
+                fn copy(&self) -> u32;
+            }
+        }
+    }
+
+    let limits = Limits { max: 1, copy: 2 };
+    let wrapper = Wrapper {
+        inner: Inner { clone: limits },
+    };
+    assert_eq!(wrapper.clone(), limits);
+    assert_eq!(wrapper.clone_ref(), &limits);
+    assert_eq!(wrapper.max(), 1);
+    assert_eq!(wrapper.max_copy(), 1);
+    assert_eq!(wrapper.copy(), 2);
+}
@@ -21,3 +21,106 @@ fn test_delegate_function() {
 
     assert_eq!(B::foo(1), 2);
 }
+
+#[test]
+fn test_delegate_function_qualified_self() {
+    trait Factory {
+        fn create(value: u32) -> Self;
+        fn name() -> &'static str;
+    }
+
+    #[derive(Debug, Default, PartialEq)]
+    struct Inner(u32);
+
+    impl Inner {
+        fn name() -> &'static str {
+            "inherent"
+        }
+    }
+
+    impl Factory for Inner {
+        fn create(value: u32) -> Self {
+            Inner(value)
+        }
+        fn name() -> &'static str {
+            "factory"
+        }
+    }
+
+    struct Wrapper;
+
+    impl Wrapper {
+        delegate! {
+            to <Inner as Factory> {
+                // <Inner as Factory>::create(value)
+                fn create(value: u32) -> Inner;
+
+                // <Inner as Factory>::name()
+                fn name() -> &'static str;
+            }
+            to <Inner as Default> {
+                // <Inner as Default>::default()
+                fn default() -> Inner;
+            }
+            to <Vec<u32>> {
+                // <Vec<u32>>::with_capacity(capacity)
+                fn with_capacity(capacity: usize) -> Vec<u32>;
+            }
+            to Inner {
+                // Inner::name()
+                #[call(name)]
+                fn inherent_name() -> &'static str;
+
+                // <Inner as Factory>::name()
+                #[through(Factory)]
+                #[call(name)]
+                fn factory_name() -> &'static str;
+
+                // <Inner as Factory>::create(value)
+                #[through(Factory)]
+                #[call(create)]
+                fn create_through(value: u32) -> Inner;
+            }
+        }
+    }
+
+    assert_eq!(Wrapper::create(1), Inner(1));
+    assert_eq!(Wrapper::name(), "factory");
+    assert_eq!(Wrapper::default(), Inner(0));
+    assert!(Wrapper::with_capacity(4).capacity() >= 4);
+    assert_eq!(Wrapper::inherent_name(), "inherent");
+    assert_eq!(Wrapper::factory_name(), "factory");
+    assert_eq!(Wrapper::create_through(2), Inner(2));
+}
+
+#[test]
+fn test_delegate_to_qualified_path_call() {
+    trait Get {
+        type Output;
+        fn get(&self) -> &Self::Output;
+    }
+
+    struct Wrapper {
+        inner: Vec<u32>,
+    }
+
+    impl Get for Wrapper {
+        type Output = Vec<u32>;
+        fn get(&self) -> &Vec<u32> {
+            &self.inner
+        }
+    }
+
+    impl Wrapper {
+        delegate! {
+            to <Wrapper as Get>::get(self) {
+                fn len(&self) -> usize;
+            }
+        }
+    }
+
+    let wrapper = Wrapper {
+        inner: vec![1, 2, 3],
+    };
+    assert_eq!(wrapper.len(), 3);
+}
@@ -105,3 +105,148 @@ fn test_lifetime_late_bound() {
         }
     }
 }
+
+#[test]
+fn test_generics_impl_trait_argument() {
+    struct Foo;
+    impl Foo {
+        fn describe<T: std::fmt::Debug>(&self, value: T, suffix: impl AsRef<str>) -> String {
+            format!("{:?}{}", value, suffix.as_ref())
+        }
+        fn parse<T: std::str::FromStr>(&self, value: impl AsRef<str>) -> Option<T> {
+            value.as_ref().parse().ok()
+        }
+    }
+
+    struct Bar(Foo);
+    impl Bar {
+        delegate! {
+            to self.0 {
+                // self.0.describe(value, suffix)
+                fn describe<T: std::fmt::Debug>(&self, value: T, suffix: impl AsRef<str>) -> String;
+
+                // self.0.parse::<T>(value)
+                #[generics(T)]
+                fn parse<T: std::str::FromStr>(&self, value: impl AsRef<str>) -> Option<T>;
+            }
+        }
+    }
+
+    let bar = Bar(Foo);
+    assert_eq!(bar.describe(1, "!"), "1!");
+    assert_eq!(bar.parse::<u32>("5"), Some(5));
+}
+
+#[test]
+fn test_generics_mapping() {
+    struct Foo;
+    impl Foo {
+        fn pair<A: Default, B: Default>(&self) -> (A, B) {
+            (A::default(), B::default())
+        }
+        fn size<T>(&self) -> usize {
+            std::mem::size_of::<T>()
+        }
+        fn convert<T: From<u8>>(&self, value: u8) -> T {
+            T::from(value)
+        }
+        fn repeat<T: Default + Copy, const N: usize>(&self) -> [T; N] {
+            [T::default(); N]
+        }
+    }
+
+    struct Bar(Foo);
+    impl Bar {
+        delegate! {
+            to self.0 {
+                // self.0.pair::<Y, X>()
+                #[generics(Y, X)]
+                fn pair<X: Default, Y: Default>(&self) -> (Y, X);
+
+                // self.0.size::<Vec<U>>()
+                #[generics(U = Vec<U>)]
+                fn size<U>(&self) -> usize;
+
+                // self.0.pair::<X, Option<Y>>()
+                #[generics(Y = Option<Y>)]
+                #[call(pair)]
+                fn pair_optional<X: Default, Y>(&self) -> (X, Option<Y>);
+
+                // self.0.repeat::<T, 2>()
+                #[generics(N = 2)]
+                #[call(repeat)]
+                fn repeat_two<T: Default + Copy, const N: usize>(&self) -> [T; 2];
+
+                // self.0.convert(value)
+                #[generics(infer)]
+                fn convert<T: From<u8>>(&self, value: u8) -> T;
+            }
+        }
+    }
+
+    let bar = Bar(Foo);
+    assert_eq!(bar.pair::<u8, String>(), (String::new(), 0));
+    assert_eq!(bar.size::<u8>(), std::mem::size_of::<Vec<u8>>());
+    assert_eq!(bar.pair_optional::<u8, String>(), (0, None));
+    assert_eq!(bar.repeat_two::<u8, 5>(), [0, 0]);
+    assert_eq!(bar.convert::<u32>(3), 3);
+}
+
+#[test]
+fn test_generics_swapped() {
+    struct Foo;
+    impl Foo {
+        fn pick<A, B>(&self) -> (&'static str, &'static str) {
+            (std::any::type_name::<A>(), std::any::type_name::<B>())
+        }
+    }
+
+    struct Bar(Foo);
+    impl Bar {
+        delegate! {
+            to self.0 {
+                // The arguments are positional, `B` is passed for `A` and `A` for `B`
+                // self.0.pick::<B, A>()
+                #[generics(B, A)]
+                fn pick<A, B>(&self) -> (&'static str, &'static str);
+            }
+        }
+    }
+
+    assert_eq!(
+        Bar(Foo).pick::<u8, String>(),
+        ("alloc::string::String", "u8")
+    );
+}
+
+#[test]
+fn test_generics_segment() {
+    struct Foo;
+    impl Foo {
+        fn first<T: Default>(&self) -> T {
+            T::default()
+        }
+        fn second<T: Default>(&self) -> T {
+            T::default()
+        }
+    }
+
+    struct Bar(Foo);
+    impl Bar {
+        delegate! {
+            #[generics(u8)]
+            to self.0 {
+                // self.0.first::<u8>()
+                fn first(&self) -> u8;
+
+                // self.0.second::<u16>()
+                #[generics(u16)]
+                fn second(&self) -> u16;
+            }
+        }
+    }
+
+    let bar = Bar(Foo);
+    assert_eq!(bar.first(), 0);
+    assert_eq!(bar.second(), 0);
+}
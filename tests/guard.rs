@@ -0,0 +1,160 @@
+use delegate::delegate;
+use std::cell::{BorrowError, Ref, RefCell, RefMut};
+use std::ops::Deref;
+
+struct Inner {
+    items: Vec<u32>,
+    name: String,
+}
+
+impl Inner {
+    fn items(&self) -> &[u32] {
+        &self.items
+    }
+    fn items_mut(&mut self) -> &mut Vec<u32> {
+        &mut self.items
+    }
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+fn inner() -> Inner {
+    Inner {
+        items: vec![1, 2],
+        name: "inner".to_string(),
+    }
+}
+
+#[test]
+fn test_guard_refcell() {
+    struct Wrapper {
+        inner: RefCell<Inner>,
+    }
+
+    impl Wrapper {
+        delegate! {
+            #[lock(refcell)]
+            #[guard]
+            to self.inner {
+                // Ref::map(self.inner.borrow(), |target| target.items())
+                fn items(&self) -> Ref<'_, [u32]>;
+
+                // RefMut::map(self.inner.borrow_mut(), |target| target.items_mut())
+                fn items_mut(&mut self) -> RefMut<'_, Vec<u32>>;
+
+                // Explicit map function
+                // std::cell::Ref::map(self.inner.borrow(), |target| target.name())
+                #[guard(std::cell::Ref::map)]
+                fn name(&self) -> impl Deref<Target = str> + '_;
+
+                #[no_inherit(guard)]
+                #[call(items)]
+                #[expr($.len())]
+                fn len(&self) -> usize;
+            }
+        }
+    }
+
+    let mut wrapper = Wrapper {
+        inner: RefCell::new(inner()),
+    };
+    wrapper.items_mut().push(3);
+    assert_eq!(&*wrapper.items(), &[1, 2, 3]);
+    assert_eq!(&*wrapper.name(), "inner");
+    assert_eq!(wrapper.len(), 3);
+}
+
+#[test]
+fn test_guard_delegator_expression() {
+    struct Wrapper {
+        inner: RefCell<Inner>,
+    }
+
+    impl Wrapper {
+        delegate! {
+            // Ref::map(self.inner.borrow(), |target| target.items())
+            #[guard]
+            to self.inner.borrow() {
+                fn items(&self) -> Ref<'_, [u32]>;
+            }
+
+            // match self.inner.try_borrow() {
+            //     Ok(guard) => Ok(Ref::map(guard, |target| target.name())),
+            //     Err(error) => Err(From::from(error)),
+            // }
+            #[lock(refcell, try)]
+            #[guard]
+            to self.inner {
+                fn name(&self) -> Result<Ref<'_, str>, BorrowError>;
+            }
+        }
+    }
+
+    let wrapper = Wrapper {
+        inner: RefCell::new(inner()),
+    };
+    assert_eq!(&*wrapper.items(), &[1, 2]);
+    assert_eq!(&*wrapper.name().unwrap(), "inner");
+    let _borrow = wrapper.inner.borrow_mut();
+    assert!(wrapper.name().is_err());
+}
+
+#[test]
+fn test_guard_async_rwlock() {
+    // The guards of the lock have to be in scope when the returned guards are not qualified
+    use tokio::sync::{RwLock, RwLockMappedWriteGuard, RwLockReadGuard, RwLockWriteGuard};
+
+    struct Wrapper {
+        inner: RwLock<Inner>,
+    }
+
+    impl Wrapper {
+        delegate! {
+            #[lock(rwlock, await)]
+            #[guard]
+            to self.inner {
+                // RwLockReadGuard::map(self.inner.read().await, |target| target.items())
+                async fn items(&self) -> RwLockReadGuard<'_, [u32]>;
+
+                // The mapped guard has a different type than the guard of the lock
+                // RwLockWriteGuard::map(self.inner.write().await, |target| target.items_mut())
+                async fn items_mut(&mut self) -> RwLockMappedWriteGuard<'_, Vec<u32>>;
+            }
+        }
+    }
+
+    let mut wrapper = Wrapper {
+        inner: RwLock::new(inner()),
+    };
+    futures::executor::block_on(async {
+        wrapper.items_mut().await.push(3);
+        assert_eq!(&*wrapper.items().await, &[1, 2, 3]);
+    });
+}
+
+#[test]
+fn test_guard_async_mutex_qualified() {
+    struct Wrapper {
+        inner: tokio::sync::Mutex<Inner>,
+    }
+
+    impl Wrapper {
+        delegate! {
+            #[lock(mutex, await)]
+            #[guard]
+            to self.inner {
+                // tokio::sync::MutexGuard::map(self.inner.lock().await, |target| target.items_mut())
+                async fn items_mut(&self) -> tokio::sync::MappedMutexGuard<'_, Vec<u32>>;
+            }
+        }
+    }
+
+    let wrapper = Wrapper {
+        inner: tokio::sync::Mutex::new(inner()),
+    };
+    futures::executor::block_on(async {
+        wrapper.items_mut().await.push(3);
+        assert_eq!(&*wrapper.items_mut().await, &[1, 2, 3]);
+    });
+}
@@ -0,0 +1,196 @@
+use delegate::delegate;
+use std::cell::{BorrowError, BorrowMutError, RefCell};
+use std::rc::Rc;
+use std::sync::{Arc, Mutex, RwLock};
+
+#[test]
+fn test_lock_modes() {
+    struct Shared {
+        mutex: Arc<Mutex<Vec<u32>>>,
+        rwlock: RwLock<Vec<u32>>,
+        refcell: Rc<RefCell<Vec<u32>>>,
+    }
+
+    impl Shared {
+        delegate! {
+            // self.mutex.lock().unwrap()
+            #[lock(mutex)]
+            to self.mutex {
+                #[call(push)]
+                fn push_mutex(&self, value: u32);
+                #[call(len)]
+                fn len_mutex(&self) -> usize;
+            }
+            // self.rwlock.read().unwrap() or self.rwlock.write().unwrap()
+            #[lock(rwlock)]
+            to self.rwlock {
+                #[call(push)]
+                fn push_rwlock(&mut self, value: u32);
+                #[call(len)]
+                fn len_rwlock(&self) -> usize;
+
+                // Write lock for a `&self` method
+                #[call(clear)]
+                #[lock(write)]
+                fn clear_rwlock(&self);
+            }
+            // self.refcell.borrow() or self.refcell.borrow_mut()
+            #[lock(refcell)]
+            to self.refcell {
+                #[call(push)]
+                fn push_refcell(&mut self, value: u32);
+                #[call(len)]
+                fn len_refcell(&self) -> usize;
+            }
+        }
+    }
+
+    let mut shared = Shared {
+        mutex: Default::default(),
+        rwlock: Default::default(),
+        refcell: Default::default(),
+    };
+    shared.push_mutex(1);
+    shared.push_rwlock(1);
+    shared.push_rwlock(2);
+    shared.push_refcell(1);
+    assert_eq!(shared.len_mutex(), 1);
+    assert_eq!(shared.len_rwlock(), 2);
+    assert_eq!(shared.len_refcell(), 1);
+    shared.clear_rwlock();
+    assert_eq!(shared.len_rwlock(), 0);
+}
+
+#[test]
+fn test_lock_errors() {
+    #[derive(Debug, PartialEq)]
+    enum Error {
+        Busy,
+        Borrow,
+    }
+
+    impl From<BorrowError> for Error {
+        fn from(_: BorrowError) -> Self {
+            Error::Borrow
+        }
+    }
+    impl From<BorrowMutError> for Error {
+        fn from(_: BorrowMutError) -> Self {
+            Error::Borrow
+        }
+    }
+
+    struct Wrapper {
+        mutex: Mutex<Vec<u32>>,
+        refcell: RefCell<Vec<u32>>,
+    }
+
+    impl Wrapper {
+        delegate! {
+            to self.mutex {
+                // match self.mutex.try_lock() { Ok(guard) => Ok(guard.len()), Err(_) => Err(Error::Busy) }
+                #[lock(mutex, try, err(Error::Busy))]
+                fn len(&self) -> Result<usize, Error>;
+
+                // match self.mutex.lock() { Ok(guard) => Ok(guard.push(value)), Err(_) => Err(Error::Busy) }
+                #[lock(mutex, err(Error::Busy))]
+                fn push(&self, value: u32) -> Result<(), Error>;
+            }
+            // match self.refcell.try_borrow() { ..., Err(error) => Err(From::from(error)) }
+            #[lock(refcell, try)]
+            to self.refcell {
+                #[call(len)]
+                fn len_refcell(&self) -> Result<usize, Error>;
+
+                #[call(push)]
+                fn push_refcell(&mut self, value: u32) -> Result<(), Error>;
+            }
+        }
+    }
+
+    let mut wrapper = Wrapper {
+        mutex: Default::default(),
+        refcell: Default::default(),
+    };
+    assert_eq!(wrapper.push(1), Ok(()));
+    assert_eq!(wrapper.len(), Ok(1));
+    {
+        let _guard = wrapper.mutex.lock().unwrap();
+        assert_eq!(wrapper.len(), Err(Error::Busy));
+    }
+
+    assert_eq!(wrapper.push_refcell(1), Ok(()));
+    assert_eq!(wrapper.len_refcell(), Ok(1));
+    let _borrow = wrapper.refcell.borrow_mut();
+    assert_eq!(wrapper.len_refcell(), Err(Error::Borrow));
+}
+
+#[test]
+fn test_lock_poisoned() {
+    #[derive(Debug, PartialEq)]
+    struct Poisoned;
+
+    struct Wrapper {
+        inner: Arc<RwLock<Vec<u32>>>,
+    }
+
+    impl Wrapper {
+        delegate! {
+            #[lock(rwlock, err(Poisoned))]
+            to self.inner {
+                fn len(&self) -> Result<usize, Poisoned>;
+            }
+        }
+    }
+
+    let wrapper = Wrapper {
+        inner: Default::default(),
+    };
+    assert_eq!(wrapper.len(), Ok(0));
+
+    let inner = wrapper.inner.clone();
+    let _ = std::thread::spawn(move || {
+        let _guard = inner.write().unwrap();
+        panic!("poison the lock");
+    })
+    .join();
+    assert_eq!(wrapper.len(), Err(Poisoned));
+}
+
+#[test]
+fn test_lock_async() {
+    struct Wrapper {
+        mutex: tokio::sync::Mutex<Vec<u32>>,
+        rwlock: tokio::sync::RwLock<Vec<u32>>,
+    }
+
+    impl Wrapper {
+        delegate! {
+            // self.mutex.lock().await.push(value)
+            #[lock(mutex, await)]
+            to self.mutex {
+                async fn push(&self, value: u32);
+                async fn len(&self) -> usize;
+            }
+            // self.rwlock.read().await or self.rwlock.write().await
+            #[lock(rwlock, await)]
+            to self.rwlock {
+                #[call(push)]
+                async fn push_rwlock(&mut self, value: u32);
+                #[call(len)]
+                async fn len_rwlock(&self) -> usize;
+            }
+        }
+    }
+
+    let mut wrapper = Wrapper {
+        mutex: Default::default(),
+        rwlock: Default::default(),
+    };
+    futures::executor::block_on(async {
+        wrapper.push(1).await;
+        wrapper.push_rwlock(1).await;
+        assert_eq!(wrapper.len().await, 1);
+        assert_eq!(wrapper.len_rwlock().await, 1);
+    });
+}
@@ -0,0 +1,128 @@
+use delegate::delegate;
+
+struct Sub {
+    values: Vec<u32>,
+}
+
+impl Sub {
+    fn first(&self) -> Option<u32> {
+        self.values.first().copied()
+    }
+}
+
+struct Inner {
+    sub: Sub,
+    name: String,
+}
+
+impl Inner {
+    fn parse(&self, value: &str) -> Result<u8, std::num::ParseIntError> {
+        value.parse()
+    }
+    fn name(&self) -> Result<&str, ()> {
+        Ok(&self.name)
+    }
+}
+
+struct Wrapper {
+    inner: Inner,
+}
+
+impl Wrapper {
+    delegate! {
+        #[unwrap]
+        to self.inner {
+            // self.inner.parse(value).unwrap()
+            fn parse(&self, value: &str) -> u8;
+
+            #[into]
+            {
+                // self.inner.parse(value).unwrap().into()
+                #[call(parse)]
+                fn parse_u32(&self, value: &str) -> u32;
+
+                // self.inner.parse(value).unwrap().into()
+                #[call(parse)]
+                fn parse_u64(&self, value: &str) -> u64;
+            }
+
+            #[no_inherit(unwrap)]
+            {
+                // self.inner.name()
+                fn name(&self) -> Result<&str, ()>;
+            }
+
+            to .sub {
+                // self.inner.sub.first().unwrap()
+                fn first(&self) -> u32;
+
+                #[field(&)]
+                to .values {
+                    // self.inner.sub.values.len()
+                    #[call]
+                    #[no_unwrap]
+                    fn len(&self) -> usize;
+                }
+            }
+
+            #[field(&)]
+            #[no_unwrap]
+            to self.inner.sub {
+                // &self.inner.sub.values
+                fn values(&self) -> &Vec<u32>;
+            }
+        }
+    }
+}
+
+#[test]
+fn test_nested_segments() {
+    let wrapper = Wrapper {
+        inner: Inner {
+            sub: Sub { values: vec![4, 5] },
+            name: "foo".to_string(),
+        },
+    };
+    assert_eq!(wrapper.parse("1"), 1);
+    assert_eq!(wrapper.parse_u32("2"), 2);
+    assert_eq!(wrapper.parse_u64("3"), 3);
+    assert_eq!(wrapper.name(), Ok("foo"));
+    assert_eq!(wrapper.first(), 4);
+    assert_eq!(wrapper.len(), 2);
+    assert_eq!(wrapper.values(), &vec![4, 5]);
+}
+
+#[test]
+fn test_nested_segment_attributes() {
+    struct Inner;
+
+    impl Inner {
+        fn foo(&self) -> u32 {
+            1
+        }
+        #[deprecated]
+        fn bar(&self) -> u32 {
+            2
+        }
+    }
+
+    struct Wrapper(Inner);
+
+    impl Wrapper {
+        delegate! {
+            #[allow(deprecated)]
+            #[cfg(any())]
+            to self.0 {
+                #[merge_attrs(skip(cfg))]
+                {
+                    fn foo(&self) -> u32;
+                    fn bar(&self) -> u32;
+                }
+            }
+        }
+    }
+
+    let wrapper = Wrapper(Inner);
+    assert_eq!(wrapper.foo(), 1);
+    assert_eq!(wrapper.bar(), 2);
+}
@@ -0,0 +1,131 @@
+use delegate::delegate;
+use std::collections::BTreeSet;
+
+#[test]
+fn test_newtype_segment() {
+    struct Set(BTreeSet<u32>);
+
+    impl Set {
+        delegate! {
+            #[newtype]
+            to self.0 {
+                // self.0.is_subset(&other.0)
+                pub fn is_subset(&self, other: &Self) -> bool;
+
+                // self.0.append(&mut other.0)
+                pub fn append(&mut self, other: &mut Self);
+
+                // self.0.eq(&other.0)
+                #[call(eq)]
+                pub fn equals(&self, other: &Self) -> bool;
+            }
+            #[newtype]
+            to &self.0 {
+                // Set(BTreeSet::union(&self.0, &other.0).copied().collect())
+                #[call(BTreeSet::union)]
+                #[expr(Set($.copied().collect()))]
+                pub fn union(&self, other: &Self) -> Self;
+            }
+        }
+    }
+
+    let set = |values: &[u32]| Set(values.iter().copied().collect());
+    let mut a = set(&[1, 2]);
+    let mut b = set(&[3]);
+    let c = a.union(&b);
+    assert_eq!(c.0.len(), 3);
+    assert!(a.is_subset(&c));
+    assert!(!c.is_subset(&a));
+
+    a.append(&mut b);
+    assert!(b.0.is_empty());
+    assert!(a.equals(&c));
+}
+
+#[test]
+fn test_newtype_method() {
+    struct Inner(u32);
+    impl Inner {
+        fn add(&self, other: Inner) -> u32 {
+            self.0 + other.0
+        }
+        fn add_optional(&self, other: Option<Inner>, offset: u32) -> u32 {
+            self.0 + other.map(|other| other.0).unwrap_or(offset)
+        }
+        fn add_raw(&self, other: u32) -> u32 {
+            self.0 + other
+        }
+        fn sum(&self, others: Vec<Inner>) -> u32 {
+            others.iter().fold(self.0, |sum, other| sum + other.0)
+        }
+    }
+
+    struct Wrapper {
+        inner: Inner,
+    }
+
+    impl Wrapper {
+        delegate! {
+            to self.inner {
+                // self.inner.add(other.inner)
+                #[newtype]
+                fn add(&self, other: Self) -> u32;
+
+                // self.inner.add_optional(other.map(|value| value.inner), offset)
+                #[newtype]
+                fn add_optional(&self, other: Option<Self>, offset: u32) -> u32;
+
+                // self.inner.add_raw(other)
+                #[newtype]
+                fn add_raw(&self, other: u32) -> u32;
+
+                // self.inner.sum(others.into_iter().map(|value| value.inner).collect())
+                #[newtype]
+                fn sum(&self, others: Vec<Self>) -> u32;
+            }
+        }
+    }
+
+    let wrapper = |value| Wrapper {
+        inner: Inner(value),
+    };
+    assert_eq!(wrapper(1).add(wrapper(2)), 3);
+    assert_eq!(wrapper(1).add_optional(Some(wrapper(2)), 5), 3);
+    assert_eq!(wrapper(1).add_optional(None, 5), 6);
+    assert_eq!(wrapper(1).add_raw(2), 3);
+    assert_eq!(wrapper(1).sum(vec![wrapper(2), wrapper(3)]), 6);
+}
+
+#[test]
+fn test_newtype_opt_out() {
+    struct Inner(u32);
+    impl Inner {
+        fn add(&self, other: &Inner) -> u32 {
+            self.0 + other.0
+        }
+        fn add_wrapper(&self, other: &Wrapper) -> u32 {
+            self.0 + other.0 .0
+        }
+    }
+
+    struct Wrapper(Inner);
+
+    impl Wrapper {
+        delegate! {
+            #[newtype]
+            to self.0 {
+                // self.0.add(&other.0)
+                fn add(&self, other: &Self) -> u32;
+
+                // self.0.add_wrapper(other)
+                #[no_inherit(newtype)]
+                fn add_wrapper(&self, other: &Self) -> u32;
+            }
+        }
+    }
+
+    let a = Wrapper(Inner(1));
+    let b = Wrapper(Inner(2));
+    assert_eq!(a.add(&b), 3);
+    assert_eq!(a.add_wrapper(&b), 3);
+}
@@ -0,0 +1,215 @@
+use delegate::delegate;
+use std::rc::{Rc, Weak};
+
+struct Inner {
+    value: u32,
+}
+
+impl Inner {
+    fn get(&self) -> u32 {
+        self.value
+    }
+    fn add(&mut self, value: u32) {
+        self.value += value;
+    }
+    fn checked_get(&self) -> Option<u32> {
+        Some(self.value)
+    }
+    fn into_value(self) -> u32 {
+        self.value
+    }
+}
+
+#[test]
+fn test_optional_fallbacks() {
+    #[derive(Debug, PartialEq)]
+    struct Missing(&'static str);
+
+    struct Wrapper {
+        inner: Option<Inner>,
+    }
+
+    impl Wrapper {
+        delegate! {
+            to? self.inner {
+                // match self.inner.as_ref() { Some(target) => Some(target.get()), None => None }
+                fn get(&self) -> Option<u32>;
+
+                // match self.inner.as_mut() { Some(target) => target.add(value), None => {} }
+                fn add(&mut self, value: u32);
+
+                #[call(get)]
+                #[fallback(default)]
+                fn get_or_default(&self) -> u32;
+
+                #[call(get)]
+                #[fallback(err(Missing("inner")))]
+                fn try_get(&self) -> Result<u32, Missing>;
+
+                // match self.inner.as_ref() { Some(target) => target.checked_get(), None => Default::default() }
+                #[fallback(default)]
+                fn checked_get(&self) -> Option<u32>;
+
+                // match self.inner { Some(target) => target.into_value(), None => panic!(...) }
+                #[fallback(panic("the wrapper is empty"))]
+                fn into_value(self) -> u32;
+            }
+        }
+    }
+
+    let mut wrapper = Wrapper {
+        inner: Some(Inner { value: 1 }),
+    };
+    wrapper.add(2);
+    assert_eq!(wrapper.get(), Some(3));
+    assert_eq!(wrapper.get_or_default(), 3);
+    assert_eq!(wrapper.try_get(), Ok(3));
+    assert_eq!(wrapper.checked_get(), Some(3));
+    assert_eq!(wrapper.into_value(), 3);
+
+    let mut empty = Wrapper { inner: None };
+    empty.add(2);
+    assert_eq!(empty.get(), None);
+    assert_eq!(empty.get_or_default(), 0);
+    assert_eq!(empty.try_get(), Err(Missing("inner")));
+    assert_eq!(empty.checked_get(), None);
+}
+
+#[test]
+#[should_panic(expected = "the wrapper is empty")]
+fn test_optional_panic() {
+    struct Wrapper {
+        inner: Option<Inner>,
+    }
+
+    impl Wrapper {
+        delegate! {
+            #[fallback(panic("the wrapper is empty"))]
+            to? self.inner {
+                fn get(&self) -> u32;
+            }
+        }
+    }
+
+    Wrapper { inner: None }.get();
+}
+
+#[test]
+#[should_panic(expected = "Cannot delegate `get`, `self.inner` is `None`")]
+fn test_optional_default_panic_message() {
+    struct Wrapper {
+        inner: Option<Inner>,
+    }
+
+    impl Wrapper {
+        delegate! {
+            to? self.inner {
+                #[fallback(panic)]
+                fn get(&self) -> u32;
+            }
+        }
+    }
+
+    Wrapper { inner: None }.get();
+}
+
+#[test]
+fn test_optional_weak() {
+    struct Wrapper {
+        inner: Weak<Inner>,
+    }
+
+    impl Wrapper {
+        delegate! {
+            // match self.inner.upgrade() { Some(target) => Some(target.get()), None => None }
+            to? self.inner.upgrade() {
+                fn get(&self) -> Option<u32>;
+            }
+        }
+    }
+
+    let inner = Rc::new(Inner { value: 1 });
+    let wrapper = Wrapper {
+        inner: Rc::downgrade(&inner),
+    };
+    assert_eq!(wrapper.get(), Some(1));
+    drop(inner);
+    assert_eq!(wrapper.get(), None);
+}
+
+#[test]
+fn test_optional_trait_object() {
+    trait Plugin {
+        fn name(&self) -> String;
+        fn priority(&self) -> u32;
+    }
+
+    struct Logger;
+    impl Plugin for Logger {
+        fn name(&self) -> String {
+            "logger".to_string()
+        }
+        fn priority(&self) -> u32 {
+            2
+        }
+    }
+
+    struct Host {
+        plugin: Option<Box<dyn Plugin>>,
+    }
+
+    impl Host {
+        delegate! {
+            #[fallback(default)]
+            to? self.plugin {
+                fn name(&self) -> String;
+
+                #[fallback(panic)]
+                fn priority(&self) -> u32;
+            }
+        }
+    }
+
+    let host = Host {
+        plugin: Some(Box::new(Logger)),
+    };
+    assert_eq!(host.name(), "logger");
+    assert_eq!(host.priority(), 2);
+    assert_eq!(Host { plugin: None }.name(), "");
+}
+
+#[test]
+fn test_optional_nested_segment() {
+    struct Wrapper {
+        inner: Inner,
+        backup: Option<Inner>,
+    }
+
+    impl Wrapper {
+        delegate! {
+            to self.inner {
+                fn get(&self) -> u32;
+
+                to? self.backup {
+                    #[call(get)]
+                    fn backup(&self) -> Option<u32>;
+
+                    // Nested segments inherit the optional delegator
+                    {
+                        #[call(get)]
+                        #[fallback(default)]
+                        fn backup_or_default(&self) -> u32;
+                    }
+                }
+            }
+        }
+    }
+
+    let wrapper = Wrapper {
+        inner: Inner { value: 1 },
+        backup: None,
+    };
+    assert_eq!(wrapper.get(), 1);
+    assert_eq!(wrapper.backup(), None);
+    assert_eq!(wrapper.backup_or_default(), 0);
+}
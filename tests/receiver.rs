@@ -0,0 +1,146 @@
+use delegate::delegate;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+#[test]
+fn test_receiver_delegators() {
+    #[derive(Clone, Default)]
+    struct Inner {
+        values: Vec<u32>,
+    }
+
+    impl Inner {
+        fn len(&self) -> usize {
+            self.values.len()
+        }
+        fn push(&mut self, value: u32) {
+            self.values.push(value);
+        }
+        fn into_values(self) -> Vec<u32> {
+            self.values
+        }
+        fn with_capacity(capacity: usize) -> Self {
+            Self {
+                values: Vec::with_capacity(capacity),
+            }
+        }
+    }
+
+    struct Wrapper {
+        inner: Rc<RefCell<Inner>>,
+    }
+
+    impl Wrapper {
+        delegate! {
+            to receiver {
+                &self => self.inner.borrow(),
+                &mut self => self.inner.borrow_mut(),
+                self => self.inner.take(),
+                Self => Inner,
+            } {
+                // self.inner.borrow().len()
+                fn len(&self) -> usize;
+                // self.inner.borrow_mut().push(value)
+                fn push(&mut self, value: u32);
+                // self.inner.take().into_values()
+                fn into_values(self) -> Vec<u32>;
+                // Inner::with_capacity(capacity)
+                fn with_capacity(capacity: usize) -> Inner;
+            }
+        }
+    }
+
+    let mut wrapper = Wrapper {
+        inner: Default::default(),
+    };
+    wrapper.push(1);
+    wrapper.push(2);
+    assert_eq!(wrapper.len(), 2);
+    assert_eq!(wrapper.into_values(), vec![1, 2]);
+    assert_eq!(Wrapper::with_capacity(4).values.capacity(), 4);
+}
+
+#[test]
+fn test_receiver_delegators_shared_entry() {
+    struct Wrapper {
+        inner: Vec<u32>,
+    }
+
+    impl Wrapper {
+        delegate! {
+            to receiver {
+                &self | &mut self => self.inner,
+                self => self.inner,
+            } {
+                fn len(&self) -> usize;
+                fn push(&mut self, value: u32);
+                #[call(into_iter)]
+                fn into_values(self) -> std::vec::IntoIter<u32>;
+            }
+        }
+    }
+
+    let mut wrapper = Wrapper { inner: vec![] };
+    wrapper.push(1);
+    assert_eq!(wrapper.len(), 1);
+    assert_eq!(wrapper.into_values().collect::<Vec<_>>(), vec![1]);
+}
+
+#[test]
+fn test_receiver_delegators_nested() {
+    struct Inner {
+        value: u32,
+    }
+
+    impl Inner {
+        fn get(&self) -> u32 {
+            self.value
+        }
+        fn set(&mut self, value: u32) {
+            self.value = value;
+        }
+    }
+
+    struct Wrapper {
+        first: RefCell<Inner>,
+        second: RefCell<Inner>,
+    }
+
+    impl Wrapper {
+        delegate! {
+            to receiver {
+                &self => self.first.borrow(),
+                &mut self => self.first.borrow_mut(),
+            } {
+                fn get(&self) -> u32;
+                fn set(&mut self, value: u32);
+
+                // Nested segments can extend each delegator
+                to .value {
+                    #[call(count_ones)]
+                    fn first_ones(&self) -> u32;
+                }
+
+                to receiver {
+                    &self => self.second.borrow(),
+                    &mut self => self.second.borrow_mut(),
+                } {
+                    #[call(get)]
+                    fn get_second(&self) -> u32;
+                    #[call(set)]
+                    fn set_second(&mut self, value: u32);
+                }
+            }
+        }
+    }
+
+    let mut wrapper = Wrapper {
+        first: RefCell::new(Inner { value: 1 }),
+        second: RefCell::new(Inner { value: 2 }),
+    };
+    wrapper.set(3);
+    wrapper.set_second(4);
+    assert_eq!(wrapper.get(), 3);
+    assert_eq!(wrapper.first_ones(), 2);
+    assert_eq!(wrapper.get_second(), 4);
+}
@@ -0,0 +1,121 @@
+use delegate::delegate;
+
+#[derive(Clone, Debug, PartialEq)]
+struct Celsius(f64);
+
+impl Celsius {
+    fn normalize(&self) -> Self {
+        Celsius(self.0.max(-273.15))
+    }
+    fn checked_add(&self, value: f64) -> Option<Self> {
+        let sum = self.0 + value;
+        if sum.is_finite() {
+            Some(Celsius(sum))
+        } else {
+            None
+        }
+    }
+    fn parse(&self, text: &str) -> Result<Self, std::num::ParseFloatError> {
+        Ok(Celsius(text.parse()?))
+    }
+    fn value(&self) -> f64 {
+        self.0
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Fahrenheit(f64);
+
+impl Fahrenheit {
+    fn normalize(&self) -> Self {
+        Fahrenheit(self.0.max(-459.67))
+    }
+    fn checked_add(&self, value: f64) -> Option<Self> {
+        Some(Fahrenheit(self.0 + value))
+    }
+    fn parse(&self, text: &str) -> Result<Self, std::num::ParseFloatError> {
+        Ok(Fahrenheit(text.parse()?))
+    }
+    fn value(&self) -> f64 {
+        self.0
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Temperature {
+    Celsius(Celsius),
+    Fahrenheit { value: Fahrenheit },
+}
+
+impl Temperature {
+    delegate! {
+        #[rewrap]
+        to match self {
+            Temperature::Celsius(c) => c,
+            Temperature::Fahrenheit { value: f } => f,
+        } {
+            // match self {
+            //     Temperature::Celsius(c) => Temperature::Celsius(c.normalize()),
+            //     Temperature::Fahrenheit { value: f } => Temperature::Fahrenheit { value: f.normalize() },
+            // }
+            fn normalize(&self) -> Self;
+
+            // match self {
+            //     Temperature::Celsius(c) => c.checked_add(value).map(|c| Temperature::Celsius(c)),
+            //     ...
+            // }
+            fn checked_add(&self, value: f64) -> Option<Self>;
+
+            // match self {
+            //     Temperature::Celsius(c) => c.parse(text).map(|c| Temperature::Celsius(c)),
+            //     ...
+            // }
+            fn parse(&self, text: &str) -> Result<Self, std::num::ParseFloatError>;
+
+            // match self {
+            //     Temperature::Celsius(c) => c.value(),
+            //     Temperature::Fahrenheit { value: f } => f.value(),
+            // }
+            #[no_inherit(rewrap)]
+            fn value(&self) -> f64;
+        }
+    }
+}
+
+#[test]
+fn test_rewrap() {
+    let celsius = Temperature::Celsius(Celsius(-300.0));
+    let fahrenheit = Temperature::Fahrenheit {
+        value: Fahrenheit(-500.0),
+    };
+    assert_eq!(celsius.normalize(), Temperature::Celsius(Celsius(-273.15)));
+    assert_eq!(
+        fahrenheit.normalize(),
+        Temperature::Fahrenheit {
+            value: Fahrenheit(-459.67)
+        }
+    );
+}
+
+#[test]
+fn test_rewrap_option_result() {
+    let celsius = Temperature::Celsius(Celsius(1.0));
+    let fahrenheit = Temperature::Fahrenheit {
+        value: Fahrenheit(1.0),
+    };
+    assert_eq!(
+        celsius.checked_add(1.0),
+        Some(Temperature::Celsius(Celsius(2.0)))
+    );
+    assert_eq!(celsius.checked_add(f64::INFINITY), None);
+    assert_eq!(
+        fahrenheit.checked_add(2.0),
+        Some(Temperature::Fahrenheit {
+            value: Fahrenheit(3.0)
+        })
+    );
+
+    assert_eq!(celsius.parse("5"), Ok(Temperature::Celsius(Celsius(5.0))));
+    assert!(fahrenheit.parse("x").is_err());
+    assert_eq!(fahrenheit.value(), 1.0);
+}
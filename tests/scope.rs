@@ -0,0 +1,146 @@
+use delegate::delegate;
+use std::cell::RefCell;
+
+struct State {
+    values: Vec<u32>,
+    name: String,
+}
+
+impl State {
+    fn len(&self) -> usize {
+        self.values.len()
+    }
+    fn push(&mut self, value: u32) {
+        self.values.push(value);
+    }
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+thread_local! {
+    static STATE: RefCell<State> = RefCell::new(State {
+        values: vec![],
+        name: "state".to_string(),
+    });
+}
+
+#[test]
+fn test_scope_thread_local() {
+    struct Handle;
+
+    impl Handle {
+        delegate! {
+            #[lock(refcell)]
+            to with STATE.with |state| {
+                // STATE.with(|state| state.borrow().len())
+                fn len(&self) -> usize;
+
+                // STATE.with(|state| state.borrow_mut().push(value))
+                fn push(&mut self, value: u32);
+
+                // The result is converted inside the closure, while the state is borrowed
+                // STATE.with(|state| Into::into(state.borrow().name()))
+                #[into]
+                fn name(&self) -> String;
+
+                // Associated functions can also use the scope
+                #[call(len)]
+                fn global_len() -> usize;
+
+                // Nested segments inherit the scope
+                // STATE.with(|state| state.borrow().len() as u64)
+                {
+                    #[call(len)]
+                    #[expr($ as u64)]
+                    fn len_u64(&self) -> u64;
+                }
+            }
+        }
+    }
+
+    let mut handle = Handle;
+    handle.push(1);
+    handle.push(2);
+    assert_eq!(handle.len(), 2);
+    assert_eq!(Handle::global_len(), 2);
+    assert_eq!(handle.name(), "state");
+    assert_eq!(handle.len_u64(), 2);
+}
+
+#[test]
+fn test_scope_propagate() {
+    #[derive(Debug, PartialEq)]
+    struct DbError;
+
+    #[derive(Debug, PartialEq)]
+    enum Error {
+        Db,
+    }
+
+    impl From<DbError> for Error {
+        fn from(_: DbError) -> Self {
+            Error::Db
+        }
+    }
+
+    struct Transaction<'a> {
+        values: &'a mut Vec<u32>,
+    }
+
+    impl Transaction<'_> {
+        fn insert(&mut self, value: u32) -> Result<usize, DbError> {
+            if value == 0 {
+                return Err(DbError);
+            }
+            self.values.push(value);
+            Ok(self.values.len())
+        }
+        fn count(&self) -> usize {
+            self.values.len()
+        }
+    }
+
+    struct Db {
+        values: Vec<u32>,
+    }
+
+    impl Db {
+        fn transaction<T>(
+            &mut self,
+            f: impl FnOnce(&mut Transaction) -> Result<T, DbError>,
+        ) -> Result<T, DbError> {
+            let mut values = self.values.clone();
+            let result = f(&mut Transaction {
+                values: &mut values,
+            })?;
+            self.values = values;
+            Ok(result)
+        }
+    }
+
+    struct Service {
+        db: Db,
+    }
+
+    impl Service {
+        delegate! {
+            // Ok(self.db.transaction(|tx| tx.insert(value))?)
+            to with self.db.transaction |tx|? {
+                fn insert(&mut self, value: u32) -> Result<usize, Error>;
+            }
+            // self.db.transaction(|tx| Ok(tx.count()))
+            to with self.db.transaction |tx| {
+                #[expr(Ok($))]
+                fn count(&mut self) -> Result<usize, DbError>;
+            }
+        }
+    }
+
+    let mut service = Service {
+        db: Db { values: vec![] },
+    };
+    assert_eq!(service.insert(1), Ok(1));
+    assert_eq!(service.insert(0), Err(Error::Db));
+    assert_eq!(service.count(), Ok(1));
+}
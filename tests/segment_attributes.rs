@@ -236,3 +236,176 @@ fn test_attribute_with_path() {
     let bar = Bar(Foo);
     assert_eq!(bar.f(), 0);
 }
+
+#[test]
+fn test_segment_expression_order() {
+    struct Inner;
+
+    impl Inner {
+        fn foo(&self) -> Result<u8, ()> {
+            Ok(1)
+        }
+        fn bar(&self) -> Result<Result<u8, ()>, ()> {
+            Ok(Ok(2))
+        }
+    }
+
+    struct Wrapper {
+        inner: Inner,
+    }
+
+    impl Wrapper {
+        delegate! {
+            #[unwrap]
+            #[into]
+            to self.inner {
+                // self.inner.foo().unwrap().into()
+                fn foo(&self) -> u32;
+                // self.inner.bar().unwrap().unwrap().into()
+                #[unwrap]
+                #[into]
+                fn bar(&self) -> u64;
+            }
+        }
+    }
+
+    let wrapper = Wrapper { inner: Inner };
+    assert_eq!(wrapper.foo(), 1);
+    assert_eq!(wrapper.bar(), 2);
+}
+
+#[test]
+fn test_segment_no_inherit() {
+    trait Named {
+        fn name(self) -> String;
+    }
+
+    #[derive(Clone, Copy)]
+    struct Inner;
+
+    impl Inner {
+        fn foo(&self) -> Result<u32, ()> {
+            Ok(1)
+        }
+        fn name(self) -> String {
+            "inherent".to_string()
+        }
+    }
+
+    impl Named for Inner {
+        fn name(self) -> String {
+            "trait".to_string()
+        }
+    }
+
+    struct Wrapper {
+        inner: Inner,
+    }
+
+    impl Wrapper {
+        delegate! {
+            #[unwrap]
+            to self.inner {
+                fn foo(&self) -> u32;
+
+                #[no_unwrap]
+                #[call(foo)]
+                fn try_foo(&self) -> Result<u32, ()>;
+            }
+            #[unwrap]
+            #[expr($ + 1)]
+            to self.inner {
+                #[no_inherit(expr)]
+                #[call(foo)]
+                fn foo_unwrapped(&self) -> u32;
+
+                #[no_inherit(unwrap, expr)]
+                #[call(foo)]
+                fn foo_result(&self) -> Result<u32, ()>;
+
+                #[no_inherit]
+                #[call(foo)]
+                fn foo_plain(&self) -> Result<u32, ()>;
+            }
+            #[through(Named)]
+            to self.inner {
+                fn name(&self) -> String;
+
+                #[no_inherit(through)]
+                #[call(name)]
+                fn inherent_name(&self) -> String;
+            }
+        }
+    }
+
+    let wrapper = Wrapper { inner: Inner };
+    assert_eq!(wrapper.foo(), 1);
+    assert_eq!(wrapper.try_foo(), Ok(1));
+    assert_eq!(wrapper.foo_unwrapped(), 1);
+    assert_eq!(wrapper.foo_result(), Ok(1));
+    assert_eq!(wrapper.foo_plain(), Ok(1));
+    assert_eq!(wrapper.name(), "trait");
+    assert_eq!(wrapper.inherent_name(), "inherent");
+}
+
+#[test]
+fn test_segment_attribute_merge_policy() {
+    struct Foo;
+
+    impl Foo {
+        fn f(&self) -> u32 {
+            0
+        }
+        #[deprecated]
+        fn old(&self) -> u32 {
+            1
+        }
+    }
+
+    struct Bar(Foo);
+
+    impl Bar {
+        delegate! {
+            /// Delegated to `Foo`.
+            #[allow(deprecated)]
+            #[must_use]
+            #[cfg(not(any()))]
+            to self.0 {
+                /// Lints and docs are appended to the method attributes.
+                #[allow(clippy::let_unit_value)]
+                fn old(&self) -> u32;
+
+                /// The method `must_use` overrides the segment one.
+                #[must_use = "the value should be used"]
+                #[allow(deprecated)]
+                #[call(old)]
+                fn old_must_use(&self) -> u32;
+            }
+            #[must_use]
+            #[allow(deprecated)]
+            #[cfg(any())]
+            #[merge_attrs(skip(must_use, cfg), override(allow))]
+            to self.0 {
+                #[allow(clippy::let_unit_value)]
+                #[call(f)]
+                fn f_without_segment_attrs(&self) -> u32;
+
+                #[call(old)]
+                fn old_again(&self) -> u32;
+            }
+            #[diagnostic::do_not_recommend]
+            #[merge_attrs(skip(diagnostic::do_not_recommend), append(must_use))]
+            to self.0 {
+                #[must_use]
+                fn f(&self) -> u32;
+            }
+        }
+    }
+
+    let bar = Bar(Foo);
+    assert_eq!(bar.old(), 1);
+    assert_eq!(bar.old_must_use(), 1);
+    assert_eq!(bar.f_without_segment_attrs(), 0);
+    assert_eq!(bar.old_again(), 1);
+    assert_eq!(bar.f(), 0);
+}
@@ -41,3 +41,71 @@ fn test_call_through_trait() {
     assert_eq!(bar.f(), 0);
     assert_eq!(bar.f2(), 1);
 }
+
+#[test]
+fn test_call_through_generic_trait() {
+    trait Convert<T> {
+        fn convert(&self) -> T;
+    }
+
+    trait Visit<'a> {
+        fn visit(&self, value: &'a str) -> usize;
+    }
+
+    struct Foo;
+
+    impl Convert<u32> for Foo {
+        fn convert(&self) -> u32 {
+            1
+        }
+    }
+    impl Convert<String> for Foo {
+        fn convert(&self) -> String {
+            "foo".to_string()
+        }
+    }
+    impl<'a> Visit<'a> for Foo {
+        fn visit(&self, value: &'a str) -> usize {
+            value.len()
+        }
+    }
+
+    struct Bar(Foo, Vec<u32>);
+
+    impl Bar {
+        delegate! {
+            to &self.0 {
+                // <_ as Convert<u32>>::convert(&self.0)
+                #[through(Convert<u32>)]
+                #[call(convert)]
+                fn number(&self) -> u32;
+
+                // <_ as Convert<String>>::convert(&self.0)
+                #[through(Convert<String>)]
+                #[call(convert)]
+                fn text(&self) -> String;
+
+                // <_ as Visit<'_>>::visit(&self.0, value)
+                #[through(for<'a> Visit<'a>)]
+                fn visit(&self, value: &str) -> usize;
+            }
+            to self.1.iter() {
+                // Iterator::count(self.1.iter())
+                #[through(Iterator<Item = &u32>)]
+                fn count(&self) -> usize;
+            }
+            to &self.1 {
+                // <_ as std::ops::Index<usize>>::index(&self.1, index)
+                #[through(std::ops::Index<usize>)]
+                fn index(&self, index: usize) -> &u32;
+            }
+        }
+    }
+
+    let bar = Bar(Foo, vec![1, 2]);
+    assert_eq!(bar.number(), 1);
+    assert_eq!(bar.text(), "foo");
+    assert_eq!(bar.visit("abc"), 3);
+    assert_eq!(bar.count(), 2);
+    assert_eq!(bar.index(1), &2);
+}
@@ -0,0 +1,136 @@
+use delegate::delegate;
+use std::time::Duration;
+
+#[derive(Debug, Default, PartialEq)]
+struct Inner {
+    timeout: Duration,
+    retries: u32,
+}
+
+impl Inner {
+    fn with_timeout(self, timeout: Duration) -> Self {
+        Self { timeout, ..self }
+    }
+    fn with_retries(self, retries: u32) -> Self {
+        Self { retries, ..self }
+    }
+}
+
+#[test]
+fn test_update_struct() {
+    #[derive(Debug, Default, PartialEq)]
+    struct Client {
+        name: String,
+        inner: Inner,
+        connections: Vec<u32>,
+    }
+
+    impl Client {
+        delegate! {
+            #[update]
+            to self.inner {
+                // Self { inner: self.inner.with_timeout(timeout), ..self }
+                fn with_timeout(self, timeout: Duration) -> Self;
+
+                // Self { inner: self.inner.with_retries(retries), ..self }
+                fn with_retries(self, retries: u32) -> Self;
+            }
+        }
+    }
+
+    let client = Client {
+        name: "client".to_string(),
+        inner: Inner::default(),
+        connections: vec![1, 2],
+    }
+    .with_timeout(Duration::from_secs(1))
+    .with_retries(3);
+    assert_eq!(client.name, "client");
+    assert_eq!(client.connections, vec![1, 2]);
+    assert_eq!(
+        client.inner,
+        Inner {
+            timeout: Duration::from_secs(1),
+            retries: 3
+        }
+    );
+}
+
+#[test]
+fn test_update_tuple_struct() {
+    struct Client(u32, Inner);
+
+    impl Client {
+        delegate! {
+            to self.1 {
+                // Self { 1: self.1.with_retries(retries), ..self }
+                #[update]
+                fn with_retries(self, retries: u32) -> Self;
+            }
+        }
+    }
+
+    let client = Client(5, Inner::default()).with_retries(2);
+    assert_eq!(client.0, 5);
+    assert_eq!(client.1.retries, 2);
+}
+
+#[test]
+fn test_update_enum() {
+    #[derive(Debug, PartialEq)]
+    enum Client {
+        Local(Inner),
+        Remote(String, Inner),
+        Pooled { size: usize, inner: Inner },
+    }
+
+    impl Client {
+        delegate! {
+            #[update]
+            to match self {
+                Client::Local(inner) => inner,
+                Client::Remote(address, inner) => inner,
+                Client::Pooled { size, inner } => inner,
+            } {
+                // match self {
+                //     Client::Local(inner) => Client::Local(inner.with_retries(retries)),
+                //     Client::Remote(address, inner) => Client::Remote(address, inner.with_retries(retries)),
+                //     Client::Pooled { size, inner } => Client::Pooled { size: size, inner: inner.with_retries(retries) },
+                // }
+                fn with_retries(self, retries: u32) -> Self;
+            }
+        }
+    }
+
+    assert_eq!(
+        Client::Local(Inner::default()).with_retries(1),
+        Client::Local(Inner {
+            retries: 1,
+            ..Inner::default()
+        })
+    );
+    assert_eq!(
+        Client::Remote("host".to_string(), Inner::default()).with_retries(2),
+        Client::Remote(
+            "host".to_string(),
+            Inner {
+                retries: 2,
+                ..Inner::default()
+            }
+        )
+    );
+    assert_eq!(
+        Client::Pooled {
+            size: 4,
+            inner: Inner::default()
+        }
+        .with_retries(3),
+        Client::Pooled {
+            size: 4,
+            inner: Inner {
+                retries: 3,
+                ..Inner::default()
+            }
+        }
+    );
+}
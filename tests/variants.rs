@@ -0,0 +1,155 @@
+use delegate::delegate;
+
+struct Circle {
+    radius: f64,
+}
+
+impl Circle {
+    fn area(&self) -> f64 {
+        3.0 * self.radius * self.radius
+    }
+    fn scale(&mut self, factor: f64) {
+        self.radius *= factor;
+    }
+    fn doubled(&self) -> Self {
+        Circle {
+            radius: self.radius * 2.0,
+        }
+    }
+}
+
+struct Square {
+    side: f64,
+}
+
+impl Square {
+    fn area(&self) -> f64 {
+        self.side * self.side
+    }
+    fn scale(&mut self, factor: f64) {
+        self.side *= factor;
+    }
+    fn doubled(&self) -> Self {
+        Square {
+            side: self.side * 2.0,
+        }
+    }
+}
+
+enum Shape {
+    Circle(Circle),
+    Square { square: Square },
+    Labeled { label: String, circle: Circle },
+}
+
+impl Shape {
+    delegate! {
+        // match self {
+        //     Shape::Circle(inner) => inner,
+        //     Shape::Square { square: inner } => inner,
+        //     Shape::Labeled { circle: inner, .. } => inner,
+        // }
+        to variants(Shape::Circle, Shape::Square { square }, Shape::Labeled { circle, .. }) {
+            fn area(&self) -> f64;
+            fn scale(&mut self, factor: f64);
+        }
+    }
+}
+
+#[test]
+fn test_variants() {
+    let mut shapes = vec![
+        Shape::Circle(Circle { radius: 1.0 }),
+        Shape::Square {
+            square: Square { side: 2.0 },
+        },
+        Shape::Labeled {
+            label: "circle".to_string(),
+            circle: Circle { radius: 2.0 },
+        },
+    ];
+    for shape in &mut shapes {
+        shape.scale(2.0);
+    }
+    let areas: Vec<f64> = shapes.iter().map(|shape| shape.area()).collect();
+    assert_eq!(areas, vec![12.0, 16.0, 48.0]);
+    if let Shape::Labeled { label, .. } = &shapes[2] {
+        assert_eq!(label, "circle");
+    }
+}
+
+#[test]
+fn test_variants_rewrap() {
+    enum SimpleShape {
+        Circle(Circle),
+        Square { square: Square },
+    }
+
+    impl SimpleShape {
+        delegate! {
+            #[rewrap]
+            to variants(Self::Circle, Self::Square { square }) {
+                // match self {
+                //     Self::Circle(inner) => Self::Circle(inner.doubled()),
+                //     Self::Square { square: inner } => Self::Square { square: inner.doubled() },
+                // }
+                fn doubled(&self) -> Self;
+
+                #[no_inherit(rewrap)]
+                {
+                    fn area(&self) -> f64;
+                }
+            }
+        }
+    }
+
+    let circle = SimpleShape::Circle(Circle { radius: 1.0 }).doubled();
+    assert_eq!(circle.area(), 12.0);
+    let square = SimpleShape::Square {
+        square: Square { side: 1.0 },
+    }
+    .doubled();
+    assert_eq!(square.area(), 4.0);
+}
+
+#[test]
+fn test_variants_function_call() {
+    struct Inner(u32);
+    impl Inner {
+        fn get(&self) -> u32 {
+            self.0
+        }
+    }
+
+    fn variants(value: u32) -> Inner {
+        Inner(value)
+    }
+
+    const DEFAULT: u32 = 5;
+
+    struct Wrapper(u32);
+    impl Wrapper {
+        delegate! {
+            // A regular function call, not the shorthand
+            to variants(self.0) {
+                fn get(&self) -> u32;
+            }
+
+            // Single identifiers are not enum variant paths, so this is a function call as well
+            to variants(DEFAULT) {
+                #[call(get)]
+                fn get_default(&self) -> u32;
+            }
+
+            // Paths with the enum are the shorthand, parentheses make it a function call
+            to (variants(u32::MIN)) {
+                #[call(get)]
+                fn get_min(&self) -> u32;
+            }
+        }
+    }
+
+    assert_eq!(Wrapper(3).get(), 3);
+    assert_eq!(Wrapper(3).get_default(), 5);
+    assert_eq!(Wrapper(3).get_min(), 0);
+}